@@ -0,0 +1,250 @@
+//! Command-line tool for querying and managing trust with the trusted accounts pallet.
+//!
+//! Queries are made against a node with the `trustedAccounts_*` RPC methods installed. Accounts
+//! are given in SS58 or as 32 bytes of hex, and blocks as 32 bytes of hex.
+
+use clap::{Parser, Subcommand, ValueEnum};
+use codec::Encode;
+use jsonrpsee::ws_client::WsClientBuilder;
+use pallet_acuity_trusted_accounts_rpc::TrustedAccountsApiClient;
+use serde::Serialize;
+use sp_core::{
+    crypto::{AccountId32, Ss58Codec},
+    H256,
+};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// The call indexes of the pallet's calls, checked against the pallet in the tests.
+const TRUST_ACCOUNT_CALL_INDEX: u8 = 0;
+const UNTRUST_ACCOUNT_CALL_INDEX: u8 = 1;
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    /// The WebSocket RPC endpoint of the node.
+    #[arg(long, global = true, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+
+    /// Query the state at this block instead of at the best block.
+    #[arg(long, global = true)]
+    at: Option<H256>,
+
+    /// How to print the result.
+    #[arg(long, global = true, value_enum, default_value_t = Output::Table)]
+    output: Output,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Output {
+    Table,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(flatten)]
+    Query(QueryCommand),
+    #[command(flatten)]
+    Call(CallCommand),
+}
+
+// Commands that query a node.
+#[derive(Subcommand)]
+enum QueryCommand {
+    /// Whether ACCOUNT trusts TRUSTEE directly.
+    IsTrusted {
+        account: AccountId32,
+        trustee: AccountId32,
+    },
+    /// Whether ACCOUNT trusts TRUSTEE directly or through an account it trusts.
+    IsTrustedDeep {
+        account: AccountId32,
+        trustee: AccountId32,
+    },
+    /// The accounts that ACCOUNT trusts.
+    TrustedBy { account: AccountId32 },
+    /// The accounts that ACCOUNT trusts that also trust TRUSTEE.
+    TrustedByThatTrust {
+        account: AccountId32,
+        trustee: AccountId32,
+    },
+    /// The shortest chain of trust from FROM to TO.
+    Path {
+        from: AccountId32,
+        to: AccountId32,
+        /// The maximum number of hops to search.
+        #[arg(long, default_value_t = 6)]
+        max_depth: u32,
+        /// The maximum number of accounts to expand.
+        #[arg(long, default_value_t = 1000)]
+        max_nodes: u32,
+    },
+}
+
+// Commands that build call data, which do not need a node.
+#[derive(Subcommand)]
+enum CallCommand {
+    /// Build the call data of an unsigned `trust_account` call.
+    TrustCall {
+        account: AccountId32,
+        /// The index of the pallet in the runtime.
+        #[arg(long)]
+        pallet_index: u8,
+    },
+    /// Build the call data of an unsigned `untrust_account` call.
+    UntrustCall {
+        account: AccountId32,
+        /// The index of the pallet in the runtime.
+        #[arg(long)]
+        pallet_index: u8,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = run(Cli::parse()).await {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    match cli.command {
+        Command::Query(command) => {
+            println!("{}", query(&cli.url, cli.at, cli.output, command).await?);
+        }
+        Command::Call(command) => {
+            println!("{}", format_call_data(cli.output, &command.call_data()));
+        }
+    }
+    Ok(())
+}
+
+// Run a query against the node and format the result.
+async fn query(
+    url: &str,
+    at: Option<H256>,
+    output: Output,
+    command: QueryCommand,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let client = WsClientBuilder::default().build(url).await?;
+
+    let result = match command {
+        QueryCommand::IsTrusted { account, trustee } => {
+            let trusted: bool = TrustedAccountsApiClient::<AccountId32, H256>::is_trusted(
+                &client, account, trustee, at,
+            )
+            .await?;
+            format_bool(output, "trusted", trusted)
+        }
+        QueryCommand::IsTrustedDeep { account, trustee } => {
+            let trusted: bool = TrustedAccountsApiClient::<AccountId32, H256>::is_trusted_deep(
+                &client, account, trustee, at,
+            )
+            .await?;
+            format_bool(output, "trusted", trusted)
+        }
+        QueryCommand::TrustedBy { account } => {
+            let accounts =
+                TrustedAccountsApiClient::<AccountId32, H256>::trusted_by(&client, account, at)
+                    .await?;
+            format_accounts(output, &accounts)
+        }
+        QueryCommand::TrustedByThatTrust { account, trustee } => {
+            let accounts = TrustedAccountsApiClient::<AccountId32, H256>::trusted_by_that_trust(
+                &client, account, trustee, at,
+            )
+            .await?;
+            format_accounts(output, &accounts)
+        }
+        QueryCommand::Path {
+            from,
+            to,
+            max_depth,
+            max_nodes,
+        } => {
+            let path = TrustedAccountsApiClient::<AccountId32, H256>::trust_path(
+                &client, from, to, max_depth, max_nodes, at,
+            )
+            .await?;
+            format_path(output, path.as_deref())
+        }
+    };
+
+    Ok(result)
+}
+
+impl CallCommand {
+    fn call_data(&self) -> Vec<u8> {
+        match self {
+            CallCommand::TrustCall {
+                account,
+                pallet_index,
+            } => call_data(*pallet_index, TRUST_ACCOUNT_CALL_INDEX, account),
+            CallCommand::UntrustCall {
+                account,
+                pallet_index,
+            } => call_data(*pallet_index, UNTRUST_ACCOUNT_CALL_INDEX, account),
+        }
+    }
+}
+
+// The SCALE encoded call: the pallet index, the call index and then the arguments.
+fn call_data(pallet_index: u8, call_index: u8, account: &AccountId32) -> Vec<u8> {
+    let mut call_data = vec![pallet_index, call_index];
+    account.encode_to(&mut call_data);
+    call_data
+}
+
+fn to_json(value: &impl Serialize) -> String {
+    serde_json::to_string_pretty(value).expect("values are always serializable")
+}
+
+fn format_bool(output: Output, name: &str, value: bool) -> String {
+    match output {
+        Output::Table => format!("{}: {}", name, if value { "yes" } else { "no" }),
+        Output::Json => to_json(&serde_json::json!({ name: value })),
+    }
+}
+
+fn format_accounts(output: Output, accounts: &[AccountId32]) -> String {
+    match output {
+        Output::Table => {
+            let mut table = format!("{:>5}  ACCOUNT", "#");
+            for (i, account) in accounts.iter().enumerate() {
+                table += &format!("\n{:>5}  {}", i, account.to_ss58check());
+            }
+            table
+        }
+        Output::Json => to_json(&accounts),
+    }
+}
+
+fn format_path(output: Output, path: Option<&[AccountId32]>) -> String {
+    match (output, path) {
+        (Output::Table, None) => "No path found".into(),
+        (Output::Table, Some(path)) => {
+            let mut table = format!("{:>5}  ACCOUNT", "HOP");
+            for (i, account) in path.iter().enumerate() {
+                table += &format!("\n{:>5}  {}", i, account.to_ss58check());
+            }
+            table
+        }
+        (Output::Json, path) => to_json(&path),
+    }
+}
+
+fn format_call_data(output: Output, call_data: &[u8]) -> String {
+    let hex = format!("0x{}", sp_core::hexdisplay::HexDisplay::from(&call_data));
+    match output {
+        Output::Table => hex,
+        Output::Json => to_json(&serde_json::json!({ "callData": hex })),
+    }
+}
@@ -0,0 +1,71 @@
+// A runtime with the pallet and 32 byte accounts, to check the call data against the pallet.
+
+use frame_support::traits::{ConstU128, ConstU16, ConstU32, ConstU64};
+use sp_core::{crypto::AccountId32, H256};
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        TrustedAccounts: pallet_acuity_trusted_accounts,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId32;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<u128>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ConstU16<42>;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u128;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU128<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type RuntimeFreezeReason = RuntimeFreezeReason;
+    type MaxHolds = ConstU32<1>;
+}
+
+impl pallet_acuity_trusted_accounts::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxTrustedAccounts = ConstU32<4>;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type TrustDeposit = ConstU128<10>;
+    type TrustLevel = u8;
+    type MaxBatchSize = ConstU32<3>;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId32>;
+    type WeightInfo = ();
+}
@@ -0,0 +1,114 @@
+use crate::{mock::*, *};
+use clap::CommandFactory;
+
+fn account(byte: u8) -> AccountId32 {
+    AccountId32::new([byte; 32])
+}
+
+#[test]
+fn cli() {
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn parse_account() {
+    let ss58 = account(1).to_ss58check();
+    let cli = Cli::try_parse_from(["trusted-accounts-cli", "trusted-by", &ss58]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Query(QueryCommand::TrustedBy { account: a }) if a == account(1)
+    ));
+
+    let hex = format!("0x{}", "02".repeat(32));
+    let cli = Cli::try_parse_from([
+        "trusted-accounts-cli",
+        "--output",
+        "json",
+        "is-trusted",
+        &ss58,
+        &hex,
+    ])
+    .unwrap();
+    assert_eq!(cli.output, Output::Json);
+    assert!(matches!(
+        cli.command,
+        Command::Query(QueryCommand::IsTrusted { account: a, trustee: t })
+            if a == account(1) && t == account(2)
+    ));
+
+    assert!(Cli::try_parse_from(["trusted-accounts-cli", "trusted-by", "nonsense"]).is_err());
+}
+
+#[test]
+fn trust_call_data() {
+    let call_data = call_data(8, TRUST_ACCOUNT_CALL_INDEX, &account(3));
+    assert_eq!(call_data.len(), 34);
+    assert_eq!(call_data[..2], [8, 0]);
+    assert_eq!(call_data[2..], [3; 32]);
+
+    let call_data = call_data_hex(8, UNTRUST_ACCOUNT_CALL_INDEX, &account(3));
+    assert_eq!(call_data, format!("0x0801{}", "03".repeat(32)));
+}
+
+#[test]
+fn call_data_matches_pallet() {
+    // The index of the pallet in the mock runtime.
+    let pallet_index = 2;
+
+    let call = RuntimeCall::TrustedAccounts(pallet_acuity_trusted_accounts::Call::trust_account {
+        account: account(3),
+    });
+    assert_eq!(
+        call_data(pallet_index, TRUST_ACCOUNT_CALL_INDEX, &account(3)),
+        call.encode()
+    );
+
+    let call =
+        RuntimeCall::TrustedAccounts(pallet_acuity_trusted_accounts::Call::untrust_account {
+            account: account(3),
+        });
+    assert_eq!(
+        call_data(pallet_index, UNTRUST_ACCOUNT_CALL_INDEX, &account(3)),
+        call.encode()
+    );
+
+    let cli = Cli::try_parse_from([
+        "trusted-accounts-cli",
+        "untrust-call",
+        "--pallet-index",
+        "2",
+        &account(3).to_ss58check(),
+    ])
+    .unwrap();
+    match cli.command {
+        Command::Call(command) => assert_eq!(command.call_data(), call.encode()),
+        Command::Query(_) => panic!("expected a call command"),
+    }
+}
+
+fn call_data_hex(pallet_index: u8, call_index: u8, account: &AccountId32) -> String {
+    format_call_data(Output::Table, &call_data(pallet_index, call_index, account))
+}
+
+#[test]
+fn format_output() {
+    assert_eq!(format_bool(Output::Table, "trusted", true), "trusted: yes");
+    assert_eq!(
+        format_bool(Output::Json, "trusted", false),
+        "{\n  \"trusted\": false\n}"
+    );
+
+    let accounts = [account(1), account(2)];
+    let table = format_accounts(Output::Table, &accounts);
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "    #  ACCOUNT");
+    assert_eq!(lines[2], format!("    1  {}", account(2).to_ss58check()));
+    let json: Vec<AccountId32> =
+        serde_json::from_str(&format_accounts(Output::Json, &accounts)).unwrap();
+    assert_eq!(json, accounts);
+
+    assert_eq!(format_path(Output::Table, None), "No path found");
+    assert_eq!(format_path(Output::Json, None), "null");
+    assert!(format_path(Output::Table, Some(&accounts)).starts_with("  HOP  ACCOUNT"));
+}
@@ -0,0 +1,318 @@
+//! SQLite storage for the indexed trust graph.
+//!
+//! Accounts and block hashes are stored SCALE encoded. The database holds a single chain of
+//! blocks from the first indexed block to the head, along with the trust changes made in each
+//! of them and the state of each trust pair before its change, so that blocks can be reverted
+//! when the best chain is reorganized.
+//!
+//! Each trust pair is stored with the block its trust expires at, if any. Pairs that have
+//! expired at the head are left out of every query until the change that prunes them is indexed.
+//!
+//! A database written with a different schema version is emptied when it is opened, and has to
+//! be indexed again.
+
+use pallet_acuity_trusted_accounts::graph::trust_path;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{fmt, path::Path};
+
+// The version of the schema, stored in the `user_version` of the database.
+const SCHEMA_VERSION: u32 = 2;
+
+const DROP_SCHEMA: &str = "
+    DROP TABLE IF EXISTS blocks;
+    DROP TABLE IF EXISTS changes;
+    DROP TABLE IF EXISTS trust;
+";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS blocks (
+        hash BLOB PRIMARY KEY,
+        number INTEGER NOT NULL,
+        parent BLOB NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS blocks_number ON blocks (number);
+    CREATE TABLE IF NOT EXISTS changes (
+        block BLOB NOT NULL,
+        seq INTEGER NOT NULL,
+        truster BLOB NOT NULL,
+        trustee BLOB NOT NULL,
+        trusted INTEGER NOT NULL,
+        previous INTEGER NOT NULL,
+        previous_expires_at INTEGER,
+        PRIMARY KEY (block, seq)
+    );
+    CREATE TABLE IF NOT EXISTS trust (
+        truster BLOB NOT NULL,
+        trustee BLOB NOT NULL,
+        expires_at INTEGER,
+        PRIMARY KEY (truster, trustee)
+    );
+    CREATE INDEX IF NOT EXISTS trust_trustee ON trust (trustee, truster);
+";
+
+/// A change to a trust pair. [truster, trustee, trusted, block the trust expires at]
+pub type Change = (Vec<u8>, Vec<u8>, bool, Option<u64>);
+
+/// Error type of the database.
+#[derive(Debug)]
+pub enum Error {
+    /// SQLite reported an error.
+    Sqlite(rusqlite::Error),
+    /// A block was applied whose parent is not the head.
+    NotChildOfHead,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Sqlite(e) => write!(f, "SQLite error: {}", e),
+            Error::NotChildOfHead => write!(f, "Block is not a child of the head"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Error {
+        Error::Sqlite(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+pub struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    /// Open the database at `path`, creating it if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Open a database that only exists in memory.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        let version: u32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version != SCHEMA_VERSION {
+            conn.execute_batch(DROP_SCHEMA)?;
+            conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        }
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// The hash and number of the last indexed block.
+    pub fn head(&self) -> Result<Option<(Vec<u8>, u64)>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT hash, number FROM blocks ORDER BY number DESC LIMIT 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?)
+    }
+
+    /// Whether the block has been indexed.
+    pub fn contains_block(&self, hash: &[u8]) -> Result<bool> {
+        Ok(self
+            .conn
+            .query_row("SELECT 1 FROM blocks WHERE hash = ?1", [hash], |_| Ok(()))
+            .optional()?
+            .is_some())
+    }
+
+    /// Index a block on top of the head, applying its trust changes in order.
+    pub fn apply_block(
+        &mut self,
+        hash: &[u8],
+        number: u64,
+        parent: &[u8],
+        changes: &[Change],
+    ) -> Result<()> {
+        // Check that the block extends the chain.
+        if let Some((head, _)) = self.head()? {
+            if head != parent {
+                return Err(Error::NotChildOfHead);
+            }
+        }
+
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO blocks (hash, number, parent) VALUES (?1, ?2, ?3)",
+            params![hash, number, parent],
+        )?;
+        for (seq, (truster, trustee, trusted, expires_at)) in changes.iter().enumerate() {
+            // Store the state of the pair before the change so that reverting restores it, even
+            // if the change did not alter it.
+            let previous = trust_state(&tx, truster, trustee)?;
+            tx.execute(
+                "INSERT INTO changes
+                    (block, seq, truster, trustee, trusted, previous, previous_expires_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    hash,
+                    seq,
+                    truster,
+                    trustee,
+                    trusted,
+                    previous.is_some(),
+                    previous.flatten()
+                ],
+            )?;
+            set_trust(&tx, truster, trustee, *trusted, *expires_at)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Remove the head block, restoring the state of each pair it changed in reverse order.
+    /// Returns the hash of the removed block.
+    pub fn revert_head(&mut self) -> Result<Option<Vec<u8>>> {
+        let hash = match self.head()? {
+            Some((hash, _)) => hash,
+            None => return Ok(None),
+        };
+
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "SELECT truster, trustee, previous, previous_expires_at FROM changes
+                    WHERE block = ?1 ORDER BY seq DESC",
+            )?;
+            let previous = stmt
+                .query_map([&hash], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                })?
+                .collect::<rusqlite::Result<Vec<Change>>>()?;
+            for (truster, trustee, trusted, expires_at) in previous {
+                set_trust(&tx, &truster, &trustee, trusted, expires_at)?;
+            }
+        }
+        tx.execute("DELETE FROM changes WHERE block = ?1", [&hash])?;
+        tx.execute("DELETE FROM blocks WHERE hash = ?1", [&hash])?;
+        tx.commit()?;
+        Ok(Some(hash))
+    }
+
+    /// Remove every indexed block and trust pair.
+    pub fn reset(&mut self) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM changes", [])?;
+        tx.execute("DELETE FROM blocks", [])?;
+        tx.execute("DELETE FROM trust", [])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Whether `truster` trusts `trustee` at the head.
+    pub fn is_trusted(&self, truster: &[u8], trustee: &[u8]) -> Result<bool> {
+        Ok(self
+            .conn
+            .prepare_cached(
+                "SELECT 1 FROM trust WHERE truster = ?1 AND trustee = ?2
+                    AND (expires_at IS NULL OR expires_at > (SELECT MAX(number) FROM blocks))",
+            )?
+            .query_row([truster, trustee], |_| Ok(()))
+            .optional()?
+            .is_some())
+    }
+
+    /// The accounts trusted by the account at the head.
+    pub fn trusted_by(&self, account: &[u8]) -> Result<Vec<Vec<u8>>> {
+        self.accounts(
+            "SELECT trustee FROM trust WHERE truster = ?1
+                AND (expires_at IS NULL OR expires_at > (SELECT MAX(number) FROM blocks))
+                ORDER BY trustee",
+            account,
+        )
+    }
+
+    /// The accounts that trust the account at the head.
+    pub fn trusting(&self, account: &[u8]) -> Result<Vec<Vec<u8>>> {
+        self.accounts(
+            "SELECT truster FROM trust WHERE trustee = ?1
+                AND (expires_at IS NULL OR expires_at > (SELECT MAX(number) FROM blocks))
+                ORDER BY truster",
+            account,
+        )
+    }
+
+    /// The accounts that trust the account and are trusted by it at the head.
+    pub fn mutually_trusted(&self, account: &[u8]) -> Result<Vec<Vec<u8>>> {
+        self.accounts(
+            "SELECT a.trustee FROM trust a
+                JOIN trust b ON b.truster = a.trustee AND b.trustee = a.truster
+                WHERE a.truster = ?1
+                AND (a.expires_at IS NULL OR a.expires_at > (SELECT MAX(number) FROM blocks))
+                AND (b.expires_at IS NULL OR b.expires_at > (SELECT MAX(number) FROM blocks))
+                ORDER BY a.trustee",
+            account,
+        )
+    }
+
+    /// The shortest chain of trust from `from` to `to`, including both, searching at most
+    /// `max_depth` hops and expanding at most `max_nodes` accounts.
+    pub fn trust_path(
+        &self,
+        from: &[u8],
+        to: &[u8],
+        max_depth: u32,
+        max_nodes: u32,
+    ) -> Result<Option<Vec<Vec<u8>>>> {
+        trust_path(
+            from.to_vec(),
+            to.to_vec(),
+            max_depth,
+            max_nodes,
+            |account| self.trusted_by(account),
+        )
+    }
+
+    fn accounts(&self, sql: &str, account: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let mut stmt = self.conn.prepare_cached(sql)?;
+        let accounts = stmt
+            .query_map([account], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(accounts)
+    }
+}
+
+// The stored state of a trust pair, whether or not it has expired: `None` if the truster does
+// not trust the trustee, otherwise the block the trust expires at, if any.
+fn trust_state(conn: &Connection, truster: &[u8], trustee: &[u8]) -> Result<Option<Option<u64>>> {
+    Ok(conn
+        .query_row(
+            "SELECT expires_at FROM trust WHERE truster = ?1 AND trustee = ?2",
+            [truster, trustee],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+// Set whether the truster trusts the trustee, and the block the trust expires at.
+fn set_trust(
+    conn: &Connection,
+    truster: &[u8],
+    trustee: &[u8],
+    trusted: bool,
+    expires_at: Option<u64>,
+) -> Result<()> {
+    if trusted {
+        conn.execute(
+            "INSERT OR REPLACE INTO trust (truster, trustee, expires_at) VALUES (?1, ?2, ?3)",
+            params![truster, trustee, expires_at],
+        )?;
+    } else {
+        conn.execute(
+            "DELETE FROM trust WHERE truster = ?1 AND trustee = ?2",
+            [truster, trustee],
+        )?;
+    }
+    Ok(())
+}
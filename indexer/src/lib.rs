@@ -0,0 +1,356 @@
+//! Off-chain index of the trust graph of the trusted accounts pallet.
+//!
+//! The indexer follows the best chain of a node, reads the trust changes of each block through
+//! `TrustedAccountsApi::all_trust_changes` and stores the resulting graph in SQLite, where it
+//! can be queried in ways that are too expensive on-chain. When the best chain is reorganized
+//! the retracted blocks are reverted before the new ones are applied.
+//!
+//! An empty database is seeded with the whole trust graph at the last finalized block, read
+//! through `TrustedAccountsApi::trust_graph`, so trust created at genesis or before the trust
+//! change api existed is included. This needs the runtime at that block to implement version 5
+//! of the api. Blocks are then indexed from there on, which needs their state. If the node has
+//! pruned the state of a finalized block that has not been indexed yet, the database is reset
+//! and seeded again at the last finalized block.
+//!
+//! The block each trust pair expires at is read through `TrustedAccountsApi::trust_expiries`,
+//! added in version 6 of the api, and expired pairs are left out of queries. Pairs indexed from
+//! a runtime with an older version are stored without an expiry, so they stay in the index until
+//! the change that prunes them is indexed.
+//!
+//! Trust pairs dropped by the v1 storage migration of the pallet emit no events, so the indexer
+//! never sees them being removed. Reset the database after that migration has run to have it
+//! seeded again without them.
+
+use codec::Codec;
+use futures::StreamExt;
+use sc_client_api::BlockchainEvents;
+use sp_api::{ApiError, ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+    traits::{Block as BlockT, Header as HeaderT},
+    SaturatedConversion,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+pub use pallet_acuity_trusted_accounts_rpc_runtime_api::TrustedAccountsApi as TrustedAccountsRuntimeApi;
+
+pub mod database;
+pub mod rpc;
+
+#[cfg(test)]
+mod tests;
+
+pub use database::Database;
+pub use rpc::{TrustedAccountsIndexer, TrustedAccountsIndexerApiServer};
+
+const LOG_TARGET: &str = "trusted-accounts-indexer";
+
+/// Error type of the indexer.
+#[derive(Debug)]
+pub enum Error {
+    /// The database failed.
+    Database(database::Error),
+    /// The node failed to look up a block.
+    Blockchain(sp_blockchain::Error),
+    /// The runtime api call failed.
+    Api(ApiError),
+    /// A block on the best chain is not known to the node.
+    UnknownBlock,
+    /// The runtime does not implement the required version of `TrustedAccountsApi`.
+    ApiUnavailable,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Database(e) => write!(f, "Database error: {}", e),
+            Error::Blockchain(e) => write!(f, "Blockchain error: {}", e),
+            Error::Api(e) => write!(f, "Runtime api error: {}", e),
+            Error::UnknownBlock => write!(f, "Unknown block"),
+            Error::ApiUnavailable => write!(f, "TrustedAccountsApi is not available"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<database::Error> for Error {
+    fn from(e: database::Error) -> Error {
+        Error::Database(e)
+    }
+}
+
+impl From<sp_blockchain::Error> for Error {
+    fn from(e: sp_blockchain::Error) -> Error {
+        Error::Blockchain(e)
+    }
+}
+
+impl From<ApiError> for Error {
+    fn from(e: ApiError) -> Error {
+        Error::Api(e)
+    }
+}
+
+/// Index the best chain of the client into the database, running until the client stops
+/// importing blocks. This should be spawned as an essential task of the node.
+///
+/// The indexing runs on blocking threads, `SYNC_BATCH_SIZE` blocks at a time, so catching up
+/// with a long chain does not hold the database lock or an async worker for long.
+pub async fn run<C, Block, AccountId>(client: Arc<C>, database: Arc<Mutex<Database>>)
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block>,
+    C: Send + Sync + 'static,
+    C::Api: TrustedAccountsRuntimeApi<Block, AccountId>,
+    AccountId: Codec + Clone + Send + 'static,
+{
+    let mut notifications = client.import_notification_stream();
+
+    loop {
+        // Catch up with the best block.
+        loop {
+            let client = client.clone();
+            let database = database.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                sync::<C, Block, AccountId>(&*client, &database, SYNC_BATCH_SIZE)
+            })
+            .await;
+            match result {
+                Ok(Ok(true)) => break,
+                Ok(Ok(false)) => continue,
+                Ok(Err(e)) => {
+                    log::error!(target: LOG_TARGET, "Failed to index the best chain: {}", e);
+                    break;
+                }
+                Err(e) => {
+                    log::error!(target: LOG_TARGET, "Indexing task failed: {}", e);
+                    return;
+                }
+            }
+        }
+
+        // Wait for a new best block.
+        loop {
+            match notifications.next().await {
+                Some(notification) if notification.is_new_best => break,
+                Some(_) => continue,
+                None => return,
+            }
+        }
+    }
+}
+
+/// The number of blocks `run` indexes at a time.
+pub const SYNC_BATCH_SIZE: u64 = 256;
+
+// The number of trusters read from the runtime at a time when seeding the database.
+const SEED_PAGE_SIZE: u32 = 1000;
+
+/// Bring the database closer to the best chain of the client: seed it if it is empty, revert
+/// indexed blocks that are no longer on the best chain and then index up to `max_blocks` new
+/// ones. Returns whether the database has caught up with the best block.
+pub fn sync<C, Block, AccountId>(
+    client: &C,
+    database: &Mutex<Database>,
+    max_blocks: u64,
+) -> Result<bool, Error>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: TrustedAccountsRuntimeApi<Block, AccountId>,
+    AccountId: Codec + Clone,
+{
+    let head = database.lock().unwrap().head()?;
+    let head_number = match head {
+        Some((head, number)) => {
+            // Revert the head if it is no longer part of the best chain.
+            let hash = client.hash(number.saturated_into())?;
+            if hash.is_none_or(|hash| hash.as_ref() != &head[..]) {
+                database.lock().unwrap().revert_head()?;
+                log::debug!(target: LOG_TARGET, "Reverted block 0x{}", hex(&head));
+                return Ok(false);
+            }
+            number
+        }
+        // Seed an empty database at the last finalized block, which cannot be reverted.
+        None => {
+            seed::<C, Block, AccountId>(client, database)?;
+            return Ok(false);
+        }
+    };
+
+    // Index the new blocks, oldest first.
+    let info = client.info();
+    let best_number: u64 = info.best_number.saturated_into();
+    let finalized_number: u64 = info.finalized_number.saturated_into();
+    let last = best_number.min(head_number.saturating_add(max_blocks));
+    for number in head_number + 1..=last {
+        let hash = client
+            .hash(number.saturated_into())?
+            .ok_or(Error::UnknownBlock)?;
+        let header = client.header(hash)?.ok_or(Error::UnknownBlock)?;
+        let changes = match block_changes::<C, Block, AccountId>(client, hash) {
+            Ok(changes) => changes,
+            // The state of finalized blocks may have been pruned, so start again from the last
+            // finalized block.
+            Err(Error::Api(e)) if number <= finalized_number => {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "Unable to read block #{}, reseeding the index: {}",
+                    number,
+                    e
+                );
+                database.lock().unwrap().reset()?;
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        };
+        match database.lock().unwrap().apply_block(
+            hash.as_ref(),
+            number,
+            header.parent_hash().as_ref(),
+            &changes,
+        ) {
+            Ok(()) => {}
+            // The best chain changed while indexing, so revert and try again.
+            Err(database::Error::NotChildOfHead) => return Ok(false),
+            Err(e) => return Err(e.into()),
+        }
+        log::debug!(target: LOG_TARGET, "Indexed block #{} {:?}", number, hash);
+    }
+
+    Ok(last == best_number)
+}
+
+// Seed an empty database with the trust graph at the last finalized block.
+fn seed<C, Block, AccountId>(client: &C, database: &Mutex<Database>) -> Result<(), Error>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: TrustedAccountsRuntimeApi<Block, AccountId>,
+    AccountId: Codec + Clone,
+{
+    let hash = client.info().finalized_hash;
+    let header = client.header(hash)?.ok_or(Error::UnknownBlock)?;
+    let number: u64 = (*header.number()).saturated_into();
+    let api = client.runtime_api();
+    let version = match api.api_version::<dyn TrustedAccountsRuntimeApi<Block, AccountId>>(hash)? {
+        Some(version) if version >= 5 => version,
+        _ => return Err(Error::ApiUnavailable),
+    };
+
+    let mut trusts = Vec::new();
+    let mut start = None;
+    loop {
+        let page = api.trust_graph(hash, start, SEED_PAGE_SIZE)?;
+        let last_page = (page.len() as u32) < SEED_PAGE_SIZE;
+        start = page.last().map(|(truster, _)| truster.clone());
+        for (truster, trustees) in page {
+            let expiries = if version >= 6 {
+                trust_expiries::<Block, _, AccountId>(&*api, hash, &truster)?
+            } else {
+                HashMap::new()
+            };
+            let truster = truster.encode();
+            trusts.extend(trustees.into_iter().map(|trustee| {
+                let trustee = trustee.encode();
+                let expires_at = expiries.get(&trustee).copied();
+                (truster.clone(), trustee, true, expires_at)
+            }));
+        }
+        if last_page {
+            break;
+        }
+    }
+
+    database.lock().unwrap().apply_block(
+        hash.as_ref(),
+        number,
+        header.parent_hash().as_ref(),
+        &trusts,
+    )?;
+    log::info!(
+        target: LOG_TARGET,
+        "Seeded the index with {} trust pairs at block #{} {:?}",
+        trusts.len(),
+        number,
+        hash
+    );
+    Ok(())
+}
+
+// Read the trust changes made in a block.
+fn block_changes<C, Block, AccountId>(
+    client: &C,
+    hash: Block::Hash,
+) -> Result<Vec<database::Change>, Error>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block>,
+    C::Api: TrustedAccountsRuntimeApi<Block, AccountId>,
+    AccountId: Codec + Clone,
+{
+    let api = client.runtime_api();
+    let version = match api.api_version::<dyn TrustedAccountsRuntimeApi<Block, AccountId>>(hash)? {
+        Some(version) if version >= 4 => version,
+        _ => return Err(Error::ApiUnavailable),
+    };
+    let changes = api.all_trust_changes(hash)?;
+
+    // Read the expiries of the pairs trusted in the block from its state, once per truster.
+    let mut expiries = HashMap::new();
+    if version >= 6 {
+        let mut trusters = HashSet::new();
+        for (truster, _, trusted) in &changes {
+            if *trusted && trusters.insert(truster.encode()) {
+                expiries.insert(
+                    truster.encode(),
+                    trust_expiries::<Block, _, AccountId>(&*api, hash, truster)?,
+                );
+            }
+        }
+    }
+
+    Ok(changes
+        .into_iter()
+        .map(|(truster, trustee, trusted)| {
+            let truster = truster.encode();
+            let trustee = trustee.encode();
+            let expires_at = match trusted {
+                true => expiries
+                    .get(&truster)
+                    .and_then(|expiries| expiries.get(&trustee))
+                    .copied(),
+                false => None,
+            };
+            (truster, trustee, trusted, expires_at)
+        })
+        .collect())
+}
+
+// The block each trust pair of the truster that has an expiry expires at, by encoded trustee.
+fn trust_expiries<Block, Api, AccountId>(
+    api: &Api,
+    hash: Block::Hash,
+    truster: &AccountId,
+) -> Result<HashMap<Vec<u8>, u64>, Error>
+where
+    Block: BlockT,
+    Api: TrustedAccountsRuntimeApi<Block, AccountId>,
+    AccountId: Codec + Clone,
+{
+    Ok(api
+        .trust_expiries(hash, truster.clone())?
+        .into_iter()
+        .map(|(trustee, expires_at)| (trustee.encode(), expires_at.saturated_into()))
+        .collect())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
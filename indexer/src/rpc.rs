@@ -0,0 +1,207 @@
+//! RPC methods that query the indexed trust graph.
+
+use crate::database::{self, Database};
+use codec::{Decode, Encode};
+use jsonrpsee::{
+    core::{Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// The last block that has been indexed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedHead<BlockHash> {
+    /// The hash of the block.
+    pub hash: BlockHash,
+    /// The number of the block.
+    pub number: u64,
+}
+
+/// The default maximum `max_depth` accepted by `trustedAccounts_indexedTrustPath`.
+pub const DEFAULT_MAX_TRUST_PATH_DEPTH: u32 = 6;
+
+/// The default maximum `max_nodes` accepted by `trustedAccounts_indexedTrustPath`.
+pub const DEFAULT_MAX_TRUST_PATH_NODES: u32 = 1000;
+
+#[rpc(client, server)]
+pub trait TrustedAccountsIndexerApi<AccountId, BlockHash> {
+    /// The last block that has been indexed. All other methods answer as of this block.
+    #[method(name = "trustedAccounts_indexedHead")]
+    fn indexed_head(&self) -> RpcResult<Option<IndexedHead<BlockHash>>>;
+
+    #[method(name = "trustedAccounts_indexedIsTrusted")]
+    fn indexed_is_trusted(&self, account: AccountId, trustee: AccountId) -> RpcResult<bool>;
+
+    #[method(name = "trustedAccounts_indexedTrustedBy")]
+    fn indexed_trusted_by(&self, account: AccountId) -> RpcResult<Vec<AccountId>>;
+
+    #[method(name = "trustedAccounts_indexedTrusting")]
+    fn indexed_trusting(&self, account: AccountId) -> RpcResult<Vec<AccountId>>;
+
+    #[method(name = "trustedAccounts_indexedMutuallyTrusted")]
+    fn indexed_mutually_trusted(&self, account: AccountId) -> RpcResult<Vec<AccountId>>;
+
+    /// Find the shortest chain of trust from `from` to `to`.
+    ///
+    /// `max_depth` and `max_nodes` are clamped to the limits the indexer was configured with.
+    #[method(name = "trustedAccounts_indexedTrustPath")]
+    fn indexed_trust_path(
+        &self,
+        from: AccountId,
+        to: AccountId,
+        max_depth: u32,
+        max_nodes: u32,
+    ) -> RpcResult<Option<Vec<AccountId>>>;
+}
+
+pub struct TrustedAccountsIndexer<AccountId, BlockHash> {
+    database: Arc<Mutex<Database>>,
+    max_trust_path_depth: u32,
+    max_trust_path_nodes: u32,
+    _marker: std::marker::PhantomData<(AccountId, BlockHash)>,
+}
+
+impl<AccountId, BlockHash> TrustedAccountsIndexer<AccountId, BlockHash> {
+    pub fn new(database: Arc<Mutex<Database>>) -> Self {
+        Self {
+            database,
+            max_trust_path_depth: DEFAULT_MAX_TRUST_PATH_DEPTH,
+            max_trust_path_nodes: DEFAULT_MAX_TRUST_PATH_NODES,
+            _marker: Default::default(),
+        }
+    }
+
+    /// Set the largest `max_depth` and `max_nodes` that a trust path query may use. Larger
+    /// values are clamped to these.
+    pub fn with_trust_path_limits(mut self, max_depth: u32, max_nodes: u32) -> Self {
+        self.max_trust_path_depth = max_depth;
+        self.max_trust_path_nodes = max_nodes;
+        self
+    }
+}
+
+/// Error type of this RPC api.
+///
+/// Codes continue on from those of `TrustedAccountsApi`.
+pub enum Error {
+    /// A value stored in the index was not decodable. Code `2`.
+    DecodeError,
+    /// The index database failed. Code `6`.
+    DatabaseError,
+}
+
+impl From<Error> for i32 {
+    fn from(e: Error) -> i32 {
+        match e {
+            Error::DecodeError => 2,
+            Error::DatabaseError => 6,
+        }
+    }
+}
+
+impl Error {
+    fn into_rpc_error(self, message: impl Into<String>, data: Option<String>) -> JsonRpseeError {
+        CallError::Custom(ErrorObject::owned(self.into(), message.into(), data)).into()
+    }
+}
+
+fn database_error(e: database::Error) -> JsonRpseeError {
+    Error::DatabaseError.into_rpc_error("Index database error", Some(e.to_string()))
+}
+
+fn decode<T: Decode>(bytes: &[u8]) -> RpcResult<T> {
+    T::decode(&mut &bytes[..]).map_err(|e| {
+        Error::DecodeError.into_rpc_error("Unable to decode indexed value", Some(e.to_string()))
+    })
+}
+
+fn decode_accounts<AccountId: Decode>(accounts: Vec<Vec<u8>>) -> RpcResult<Vec<AccountId>> {
+    accounts.iter().map(|account| decode(account)).collect()
+}
+
+impl<AccountId, BlockHash> TrustedAccountsIndexerApiServer<AccountId, BlockHash>
+    for TrustedAccountsIndexer<AccountId, BlockHash>
+where
+    AccountId: Encode + Decode + Send + Sync + Serialize + DeserializeOwned + 'static,
+    BlockHash: Decode + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+    fn indexed_head(&self) -> RpcResult<Option<IndexedHead<BlockHash>>> {
+        let head = self
+            .database
+            .lock()
+            .unwrap()
+            .head()
+            .map_err(database_error)?;
+
+        match head {
+            Some((hash, number)) => Ok(Some(IndexedHead {
+                hash: decode(&hash)?,
+                number,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    fn indexed_is_trusted(&self, account: AccountId, trustee: AccountId) -> RpcResult<bool> {
+        self.database
+            .lock()
+            .unwrap()
+            .is_trusted(&account.encode(), &trustee.encode())
+            .map_err(database_error)
+    }
+
+    fn indexed_trusted_by(&self, account: AccountId) -> RpcResult<Vec<AccountId>> {
+        let accounts = self
+            .database
+            .lock()
+            .unwrap()
+            .trusted_by(&account.encode())
+            .map_err(database_error)?;
+
+        decode_accounts(accounts)
+    }
+
+    fn indexed_trusting(&self, account: AccountId) -> RpcResult<Vec<AccountId>> {
+        let accounts = self
+            .database
+            .lock()
+            .unwrap()
+            .trusting(&account.encode())
+            .map_err(database_error)?;
+
+        decode_accounts(accounts)
+    }
+
+    fn indexed_mutually_trusted(&self, account: AccountId) -> RpcResult<Vec<AccountId>> {
+        let accounts = self
+            .database
+            .lock()
+            .unwrap()
+            .mutually_trusted(&account.encode())
+            .map_err(database_error)?;
+
+        decode_accounts(accounts)
+    }
+
+    fn indexed_trust_path(
+        &self,
+        from: AccountId,
+        to: AccountId,
+        max_depth: u32,
+        max_nodes: u32,
+    ) -> RpcResult<Option<Vec<AccountId>>> {
+        let max_depth = max_depth.min(self.max_trust_path_depth);
+        let max_nodes = max_nodes.min(self.max_trust_path_nodes);
+        let path = self
+            .database
+            .lock()
+            .unwrap()
+            .trust_path(&from.encode(), &to.encode(), max_depth, max_nodes)
+            .map_err(database_error)?;
+
+        path.map(decode_accounts).transpose()
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{
+    database::{Change, Database, Error},
+    rpc::{TrustedAccountsIndexer, TrustedAccountsIndexerApiServer},
+};
+use std::sync::{Arc, Mutex};
+
+fn account(n: u8) -> Vec<u8> {
+    vec![n]
+}
+
+fn block(n: u8) -> Vec<u8> {
+    vec![0xb0, n]
+}
+
+fn trust(truster: u8, trustee: u8) -> Change {
+    (account(truster), account(trustee), true, None)
+}
+
+fn trust_until(truster: u8, trustee: u8, expires_at: u64) -> Change {
+    (account(truster), account(trustee), true, Some(expires_at))
+}
+
+fn untrust(truster: u8, trustee: u8) -> Change {
+    (account(truster), account(trustee), false, None)
+}
+
+#[test]
+fn apply_block() {
+    let mut db = Database::open_in_memory().unwrap();
+    assert_eq!(db.head().unwrap(), None);
+
+    db.apply_block(&block(0), 0, &[], &[]).unwrap();
+    db.apply_block(
+        &block(1),
+        1,
+        &block(0),
+        &[trust(1, 2), trust(1, 3), trust(2, 3)],
+    )
+    .unwrap();
+    assert_eq!(db.head().unwrap(), Some((block(1), 1)));
+    assert!(db.contains_block(&block(1)).unwrap());
+    assert!(db.is_trusted(&account(1), &account(2)).unwrap());
+    assert_eq!(
+        db.trusted_by(&account(1)).unwrap(),
+        vec![account(2), account(3)]
+    );
+    assert_eq!(
+        db.trusting(&account(3)).unwrap(),
+        vec![account(1), account(2)]
+    );
+
+    db.apply_block(&block(2), 2, &block(1), &[untrust(1, 2), trust(3, 1)])
+        .unwrap();
+    assert!(!db.is_trusted(&account(1), &account(2)).unwrap());
+    assert_eq!(db.trusted_by(&account(1)).unwrap(), vec![account(3)]);
+    assert_eq!(db.mutually_trusted(&account(1)).unwrap(), vec![account(3)]);
+}
+
+#[test]
+fn apply_block_not_child_of_head() {
+    let mut db = Database::open_in_memory().unwrap();
+    db.apply_block(&block(0), 0, &[], &[]).unwrap();
+
+    assert!(matches!(
+        db.apply_block(&block(2), 2, &block(1), &[trust(1, 2)]),
+        Err(Error::NotChildOfHead)
+    ));
+    assert!(!db.is_trusted(&account(1), &account(2)).unwrap());
+}
+
+#[test]
+fn revert_head() {
+    let mut db = Database::open_in_memory().unwrap();
+    db.apply_block(&block(0), 0, &[], &[]).unwrap();
+    db.apply_block(&block(1), 1, &block(0), &[trust(1, 2), trust(1, 3)])
+        .unwrap();
+    // Trust and untrust within a block must be undone in reverse order.
+    db.apply_block(
+        &block(2),
+        2,
+        &block(1),
+        &[
+            untrust(1, 2),
+            trust(1, 4),
+            untrust(1, 4),
+            trust(1, 2),
+            untrust(1, 3),
+        ],
+    )
+    .unwrap();
+    assert_eq!(db.trusted_by(&account(1)).unwrap(), vec![account(2)]);
+
+    assert_eq!(db.revert_head().unwrap(), Some(block(2)));
+    assert_eq!(db.head().unwrap(), Some((block(1), 1)));
+    assert!(!db.contains_block(&block(2)).unwrap());
+    assert_eq!(
+        db.trusted_by(&account(1)).unwrap(),
+        vec![account(2), account(3)]
+    );
+
+    // A different block can now be applied at the same height.
+    db.apply_block(&block(3), 2, &block(1), &[trust(2, 3)])
+        .unwrap();
+    assert_eq!(
+        db.trusting(&account(3)).unwrap(),
+        vec![account(1), account(2)]
+    );
+
+    db.revert_head().unwrap();
+    db.revert_head().unwrap();
+    db.revert_head().unwrap();
+    assert_eq!(db.revert_head().unwrap(), None);
+    assert!(db.trusting(&account(3)).unwrap().is_empty());
+}
+
+#[test]
+fn revert_head_restores_previous_state() {
+    let mut db = Database::open_in_memory().unwrap();
+    db.apply_block(&block(0), 0, &[], &[trust(1, 2)]).unwrap();
+    // Changes that do not alter the state of a pair, such as trust replacing an expired pair
+    // that was never pruned.
+    db.apply_block(&block(1), 1, &block(0), &[trust(1, 2), untrust(1, 3)])
+        .unwrap();
+
+    db.revert_head().unwrap();
+    assert!(db.is_trusted(&account(1), &account(2)).unwrap());
+    assert!(!db.is_trusted(&account(1), &account(3)).unwrap());
+}
+
+#[test]
+fn expired_trust_is_ignored() {
+    let mut db = Database::open_in_memory().unwrap();
+    db.apply_block(
+        &block(0),
+        0,
+        &[],
+        &[
+            trust_until(1, 2, 2),
+            trust(1, 3),
+            trust(2, 1),
+            trust_until(3, 1, 3),
+        ],
+    )
+    .unwrap();
+    assert!(db.is_trusted(&account(1), &account(2)).unwrap());
+    assert_eq!(
+        db.trusted_by(&account(1)).unwrap(),
+        vec![account(2), account(3)]
+    );
+    assert_eq!(
+        db.mutually_trusted(&account(1)).unwrap(),
+        vec![account(2), account(3)]
+    );
+
+    // Trust has expired once the head reaches its expiry, even though it is not pruned yet.
+    db.apply_block(&block(2), 2, &block(0), &[]).unwrap();
+    assert!(!db.is_trusted(&account(1), &account(2)).unwrap());
+    assert_eq!(db.trusted_by(&account(1)).unwrap(), vec![account(3)]);
+    assert_eq!(
+        db.trusting(&account(1)).unwrap(),
+        vec![account(2), account(3)]
+    );
+    assert_eq!(db.mutually_trusted(&account(1)).unwrap(), vec![account(3)]);
+    assert_eq!(
+        db.trust_path(&account(2), &account(2), 0, 0).unwrap(),
+        Some(vec![account(2)])
+    );
+    assert_eq!(
+        db.trust_path(&account(1), &account(2), 5, 10).unwrap(),
+        None
+    );
+
+    db.apply_block(&block(3), 3, &block(2), &[]).unwrap();
+    assert!(db.trusting(&account(1)).unwrap().contains(&account(2)));
+    assert!(!db.trusting(&account(1)).unwrap().contains(&account(3)));
+
+    // Reverting the head makes the trust valid again.
+    db.revert_head().unwrap();
+    db.revert_head().unwrap();
+    assert!(db.is_trusted(&account(1), &account(2)).unwrap());
+}
+
+#[test]
+fn revert_head_restores_expiry() {
+    let mut db = Database::open_in_memory().unwrap();
+    db.apply_block(&block(0), 0, &[], &[trust_until(1, 2, 5)])
+        .unwrap();
+    // The expired pair is replaced by trust without an expiry.
+    db.apply_block(&block(5), 5, &block(0), &[untrust(1, 2), trust(1, 2)])
+        .unwrap();
+    assert!(db.is_trusted(&account(1), &account(2)).unwrap());
+
+    db.revert_head().unwrap();
+    db.apply_block(&block(6), 5, &block(0), &[]).unwrap();
+    assert!(!db.is_trusted(&account(1), &account(2)).unwrap());
+}
+
+#[test]
+fn reset() {
+    let mut db = Database::open_in_memory().unwrap();
+    db.apply_block(&block(0), 0, &[], &[trust(1, 2)]).unwrap();
+    db.apply_block(&block(1), 1, &block(0), &[trust(2, 3)])
+        .unwrap();
+
+    db.reset().unwrap();
+    assert_eq!(db.head().unwrap(), None);
+    assert!(!db.is_trusted(&account(1), &account(2)).unwrap());
+
+    // Any block can be applied to the empty database.
+    db.apply_block(&block(5), 5, &block(4), &[trust(1, 3)])
+        .unwrap();
+    assert_eq!(db.trusting(&account(3)).unwrap(), vec![account(1)]);
+}
+
+#[test]
+fn trust_path() {
+    let mut db = Database::open_in_memory().unwrap();
+    db.apply_block(
+        &block(0),
+        0,
+        &[],
+        &[
+            trust(1, 2),
+            trust(2, 3),
+            trust(3, 4),
+            trust(1, 5),
+            trust(5, 4),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        db.trust_path(&account(1), &account(1), 0, 0).unwrap(),
+        Some(vec![account(1)])
+    );
+    assert_eq!(
+        db.trust_path(&account(1), &account(4), 3, 10).unwrap(),
+        Some(vec![account(1), account(5), account(4)])
+    );
+    assert_eq!(
+        db.trust_path(&account(2), &account(4), 2, 10).unwrap(),
+        Some(vec![account(2), account(3), account(4)])
+    );
+    assert_eq!(
+        db.trust_path(&account(2), &account(4), 1, 10).unwrap(),
+        None
+    );
+    assert_eq!(
+        db.trust_path(&account(4), &account(1), 5, 10).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn indexed_trust_path_is_clamped() {
+    let mut db = Database::open_in_memory().unwrap();
+    db.apply_block(&block(0), 0, &[], &[trust(1, 2), trust(2, 3), trust(3, 4)])
+        .unwrap();
+    let indexer = TrustedAccountsIndexer::<u8, [u8; 2]>::new(Arc::new(Mutex::new(db)));
+
+    assert_eq!(
+        indexer
+            .indexed_trust_path(1, 4, u32::MAX, u32::MAX)
+            .unwrap(),
+        Some(vec![1, 2, 3, 4])
+    );
+
+    let indexer = indexer.with_trust_path_limits(2, u32::MAX);
+    assert_eq!(
+        indexer
+            .indexed_trust_path(1, 4, u32::MAX, u32::MAX)
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        indexer
+            .indexed_trust_path(1, 3, u32::MAX, u32::MAX)
+            .unwrap(),
+        Some(vec![1, 2, 3])
+    );
+
+    let indexer = indexer.with_trust_path_limits(u32::MAX, 1);
+    assert_eq!(
+        indexer
+            .indexed_trust_path(1, 3, u32::MAX, u32::MAX)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn open_persists() {
+    let path = std::env::temp_dir().join(format!(
+        "trusted-accounts-indexer-test-{}.sqlite",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    {
+        let mut db = Database::open(&path).unwrap();
+        db.apply_block(&block(0), 0, &[], &[trust(1, 2)]).unwrap();
+    }
+    let db = Database::open(&path).unwrap();
+    assert_eq!(db.head().unwrap(), Some((block(0), 0)));
+    assert!(db.is_trusted(&account(1), &account(2)).unwrap());
+    drop(db);
+
+    // A database with a different schema version is emptied.
+    rusqlite::Connection::open(&path)
+        .unwrap()
+        .pragma_update(None, "user_version", 0)
+        .unwrap();
+    let db = Database::open(&path).unwrap();
+    assert_eq!(db.head().unwrap(), None);
+    assert!(!db.is_trusted(&account(1), &account(2)).unwrap());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -1,9 +1,18 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use sp_runtime::traits::NumberFor;
 #[cfg(not(feature = "std"))]
 use sp_std::prelude::Vec;
 
+pub mod snapshot;
+
 sp_api::decl_runtime_apis! {
+    /// The current version is 6. Methods added after version 1 are marked with the version that
+    /// added them, and clients should check the version a runtime reports before calling them.
+    /// Runtimes implement the current version with `#[api_version(6)]` on their `impl` block.
+    ///
+    /// The version is not also set on the trait, as the methods of a trait cannot be marked with
+    /// versions up to its own.
     pub trait TrustedAccountsApi<AccountId> where
         AccountId: codec::Codec,
     {
@@ -12,5 +21,21 @@ sp_api::decl_runtime_apis! {
          fn is_trusted_deep(account: AccountId, trustee: AccountId) -> bool;
          fn trusted_by(account: AccountId) -> Vec<AccountId>;
          fn trusted_by_that_trust(account: AccountId, account_is_trusted_by_trusted: AccountId) -> Vec<AccountId>;
+         #[api_version(2)]
+         fn trusting(account: AccountId) -> Vec<AccountId>;
+         #[api_version(2)]
+         fn trusting_count(account: AccountId) -> u32;
+         #[api_version(2)]
+         fn trust_path(from: AccountId, to: AccountId, max_depth: u32, max_nodes: u32) -> Option<Vec<AccountId>>;
+         #[api_version(2)]
+         fn trusted_by_paged(account: AccountId, start: u32, limit: u32) -> (Vec<AccountId>, u32);
+         #[api_version(3)]
+         fn trust_changes(account: AccountId) -> Vec<(AccountId, AccountId, bool)>;
+         #[api_version(4)]
+         fn all_trust_changes() -> Vec<(AccountId, AccountId, bool)>;
+         #[api_version(5)]
+         fn trust_graph(start: Option<AccountId>, limit: u32) -> Vec<(AccountId, Vec<AccountId>)>;
+         #[api_version(6)]
+         fn trust_expiries(account: AccountId) -> Vec<(AccountId, NumberFor<Block>)>;
     }
 }
@@ -0,0 +1,112 @@
+//! A versioned format for exporting and importing the trust graph.
+//!
+//! A snapshot holds every account that trusts at least one other account, in storage order,
+//! together with the accounts it trusts in the order it trusted them. It is either SCALE
+//! encoded or serialized as JSON, with the fields in the order below:
+//!
+//! - `header`
+//!   - `version`: `u32`, always [`SNAPSHOT_VERSION`] for this format.
+//!   - `blockHash`: the hash of the block the graph was exported at.
+//!   - `trusters`: `u32`, the number of entries in `trusts`.
+//!   - `trustPairs`: `u32`, the total number of trusted accounts in `trusts`.
+//! - `trusts`: a list of `[truster, [trustee, ...]]`.
+//!
+//! As `version` is the first field, it can be decoded on its own to reject snapshots in a
+//! format that is not understood. Accounts and hashes are SCALE encoded values of the runtime's
+//! types, and in JSON use their serde representation.
+//!
+//! A snapshot can be imported into a new chain with the pallet's `GenesisConfig`, using the
+//! pairs returned by [`Snapshot::into_pairs`], or into a running one with batched `force_trust`
+//! calls. Trust levels, expiries and deposits are not included.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::prelude::Vec;
+
+/// The version of the snapshot format described in this module.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// The header of a snapshot.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SnapshotHeader<BlockHash> {
+    /// The version of the snapshot format.
+    pub version: u32,
+    /// The block the graph was exported at.
+    pub block_hash: BlockHash,
+    /// The number of accounts that trust at least one other account.
+    pub trusters: u32,
+    /// The total number of trust pairs.
+    pub trust_pairs: u32,
+}
+
+/// A snapshot of the trust graph.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Snapshot<AccountId, BlockHash> {
+    pub header: SnapshotHeader<BlockHash>,
+    /// Each truster with the accounts it trusts. [truster, trustees]
+    pub trusts: Vec<(AccountId, Vec<AccountId>)>,
+}
+
+/// Why a snapshot could not be used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The snapshot is in a version of the format that is not understood.
+    UnsupportedVersion(u32),
+    /// The sizes in the header do not match the graph.
+    SizeMismatch,
+}
+
+impl<AccountId, BlockHash> Snapshot<AccountId, BlockHash> {
+    /// Create a snapshot of the graph at the block, filling in the header.
+    pub fn new(block_hash: BlockHash, trusts: Vec<(AccountId, Vec<AccountId>)>) -> Self {
+        let trust_pairs = trusts
+            .iter()
+            .map(|(_, trustees)| trustees.len() as u32)
+            .sum();
+
+        Snapshot {
+            header: SnapshotHeader {
+                version: SNAPSHOT_VERSION,
+                block_hash,
+                trusters: trusts.len() as u32,
+                trust_pairs,
+            },
+            trusts,
+        }
+    }
+
+    /// Check that the snapshot is in this version of the format and that its header matches
+    /// the graph.
+    pub fn validate(&self) -> Result<(), SnapshotError> {
+        if self.header.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(self.header.version));
+        }
+        let trust_pairs: usize = self.trusts.iter().map(|(_, trustees)| trustees.len()).sum();
+        if self.header.trusters as usize != self.trusts.len()
+            || self.header.trust_pairs as usize != trust_pairs
+        {
+            return Err(SnapshotError::SizeMismatch);
+        }
+        Ok(())
+    }
+
+    /// The trust pairs of the graph in order, as used by the pallet's `GenesisConfig`.
+    /// [truster, trustee]
+    pub fn into_pairs(self) -> Vec<(AccountId, AccountId)>
+    where
+        AccountId: Clone,
+    {
+        self.trusts
+            .into_iter()
+            .flat_map(|(truster, trustees)| {
+                trustees
+                    .into_iter()
+                    .map(move |trustee| (truster.clone(), trustee))
+            })
+            .collect()
+    }
+}
@@ -1,16 +1,82 @@
 use codec::Codec;
+use futures::{future, stream, FutureExt, Stream, StreamExt};
 use jsonrpsee::{
-    core::RpcResult,
+    core::{error::SubscriptionClosed, Error as JsonRpseeError, RpcResult},
     proc_macros::rpc,
-    types::error::{CallError, ErrorObject},
+    types::{
+        error::{CallError, ErrorObject, ErrorObjectOwned},
+        SubscriptionResult,
+    },
+    SubscriptionSink,
 };
-use sp_api::ProvideRuntimeApi;
+use sc_client_api::BlockchainEvents;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_api::{ApiError, ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
+use sp_core::traits::SpawnNamed;
 use sp_runtime::traits::Block as BlockT;
 
 use std::sync::Arc;
 
-pub use pallet_acuity_trusted_accounts_rpc_runtime_api::TrustedAccountsApi as TrustedAccountsRuntimeApi;
+#[cfg(test)]
+mod tests;
+
+pub use pallet_acuity_trusted_accounts_rpc_runtime_api::{
+    snapshot::Snapshot, TrustedAccountsApi as TrustedAccountsRuntimeApi,
+};
+
+/// The most trusters returned in a page of `trustedAccounts_snapshotPage`.
+pub const MAX_SNAPSHOT_PAGE_SIZE: u32 = 1000;
+
+/// The default maximum `max_depth` accepted by `trustedAccounts_trustPath`.
+pub const DEFAULT_MAX_TRUST_PATH_DEPTH: u32 = 6;
+
+/// The default maximum `max_nodes` accepted by `trustedAccounts_trustPath`.
+pub const DEFAULT_MAX_TRUST_PATH_NODES: u32 = 1000;
+
+/// A page of the accounts trusted by an account.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustedByPage<AccountId> {
+    /// The trusted accounts in this page.
+    pub accounts: Vec<AccountId>,
+    /// The total number of accounts trusted by the account.
+    pub total: u32,
+    /// The cursor to pass to get the next page, if there is one. It is only valid at the same
+    /// block.
+    pub next: Option<u32>,
+}
+
+/// A page of the trust graph in the snapshot format.
+///
+/// To export a whole snapshot, request pages at the `block_hash` of the first page, passing
+/// `next` as the start of each following page, and pass the block hash and the concatenated
+/// `trusts` to `Snapshot::new`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotPage<AccountId, BlockHash> {
+    /// The block the page was read at.
+    pub block_hash: BlockHash,
+    /// Each truster in this page with the accounts it trusts. [truster, trustees]
+    pub trusts: Vec<(AccountId, Vec<AccountId>)>,
+    /// The start to pass to get the next page, if there is one. It is only valid at the same
+    /// block.
+    pub next: Option<AccountId>,
+}
+
+/// A change to a trust pair involving a subscribed account.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustChange<AccountId, BlockHash> {
+    /// The block in which the change happened.
+    pub block_hash: BlockHash,
+    /// The account that trusts or no longer trusts the trustee.
+    pub truster: AccountId,
+    /// The account that is or is no longer trusted.
+    pub trustee: AccountId,
+    /// Whether the truster now trusts the trustee.
+    pub trusted: bool,
+}
 
 #[rpc(client, server)]
 pub trait TrustedAccountsApi<AccountId, BlockHash> {
@@ -41,6 +107,21 @@ pub trait TrustedAccountsApi<AccountId, BlockHash> {
     #[method(name = "trustedAccounts_trustedBy")]
     fn trusted_by(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<AccountId>>;
 
+    /// Page through the accounts trusted by an account.
+    ///
+    /// The cursor is an index into the account's trust list, which is not stable: untrusting an
+    /// account moves the last entry of the list into its place. If the list changes between
+    /// calls an account can be skipped or returned twice, so pass the same `at` block for every
+    /// page to get a consistent result.
+    #[method(name = "trustedAccounts_trustedByPaged")]
+    fn trusted_by_paged(
+        &self,
+        account: AccountId,
+        cursor: Option<u32>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<TrustedByPage<AccountId>>;
+
     #[method(name = "trustedAccounts_trustedByThatTrust")]
     fn trusted_by_that_trust(
         &self,
@@ -48,10 +129,54 @@ pub trait TrustedAccountsApi<AccountId, BlockHash> {
         account_is_trusted_by_trusted: AccountId,
         at: Option<BlockHash>,
     ) -> RpcResult<Vec<AccountId>>;
+
+    #[method(name = "trustedAccounts_trusting")]
+    fn trusting(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Vec<AccountId>>;
+
+    #[method(name = "trustedAccounts_trustingCount")]
+    fn trusting_count(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<u32>;
+
+    /// Find the shortest chain of trust from `from` to `to`.
+    ///
+    /// `max_depth` and `max_nodes` are clamped to the limits the node was configured with.
+    #[method(name = "trustedAccounts_trustPath")]
+    fn trust_path(
+        &self,
+        from: AccountId,
+        to: AccountId,
+        max_depth: u32,
+        max_nodes: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<Vec<AccountId>>>;
+
+    /// Export a page of up to `limit` trusters of the trust graph in the snapshot format,
+    /// starting after the truster `start`. `limit` is capped at `MAX_SNAPSHOT_PAGE_SIZE`.
+    #[method(name = "trustedAccounts_snapshotPage")]
+    fn snapshot_page(
+        &self,
+        start: Option<AccountId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<SnapshotPage<AccountId, BlockHash>>;
+
+    /// Stream trust pairs that the account is part of as they change in new best blocks.
+    ///
+    /// When the best chain is re-organized the changes in the newly enacted blocks are sent, but
+    /// changes in retracted blocks are not undone. If the changes of a block cannot be read the
+    /// subscription is closed with an error.
+    #[subscription(
+        name = "trustedAccounts_subscribeTrustChanges" => "trustedAccounts_trustChange",
+        unsubscribe = "trustedAccounts_unsubscribeTrustChanges",
+        item = TrustChange<AccountId, BlockHash>
+    )]
+    fn subscribe_trust_changes(&self, account: AccountId);
 }
 
 pub struct TrustedAccounts<C, P> {
     client: Arc<C>,
+    executor: Option<Arc<dyn SpawnNamed>>,
+    max_trust_path_depth: u32,
+    max_trust_path_nodes: u32,
     _marker: std::marker::PhantomData<P>,
 }
 
@@ -59,17 +184,43 @@ impl<C, P> TrustedAccounts<C, P> {
     pub fn new(client: Arc<C>) -> Self {
         Self {
             client,
+            executor: None,
+            max_trust_path_depth: DEFAULT_MAX_TRUST_PATH_DEPTH,
+            max_trust_path_nodes: DEFAULT_MAX_TRUST_PATH_NODES,
             _marker: Default::default(),
         }
     }
+
+    /// Enable subscriptions, which are run as tasks spawned with `executor`.
+    pub fn with_executor(mut self, executor: Arc<dyn SpawnNamed>) -> Self {
+        self.executor = Some(executor);
+        self
+    }
+
+    /// Set the largest `max_depth` and `max_nodes` that a trust path query may use. Larger
+    /// values are clamped to these.
+    pub fn with_trust_path_limits(mut self, max_depth: u32, max_nodes: u32) -> Self {
+        self.max_trust_path_depth = max_depth;
+        self.max_trust_path_nodes = max_nodes;
+        self
+    }
 }
 
 /// Error type of this RPC api.
+///
+/// Each variant is reported with its own JSON-RPC error code.
 pub enum Error {
-    /// The transaction was not decodable.
+    /// A runtime api parameter or return value could not be converted or decoded. Code `2`.
     DecodeError,
-    /// The call to runtime failed.
+    /// The call to runtime failed. Code `1`.
     RuntimeError,
+    /// The requested block is not known to the node. Code `3`.
+    UnknownBlock,
+    /// The runtime at the requested block does not implement the required version of
+    /// `TrustedAccountsApi`. Code `4`.
+    ApiUnavailable,
+    /// The node was not set up to run subscriptions. Code `5`.
+    SubscriptionsUnavailable,
 }
 
 impl From<Error> for i32 {
@@ -77,6 +228,93 @@ impl From<Error> for i32 {
         match e {
             Error::RuntimeError => 1,
             Error::DecodeError => 2,
+            Error::UnknownBlock => 3,
+            Error::ApiUnavailable => 4,
+            Error::SubscriptionsUnavailable => 5,
+        }
+    }
+}
+
+impl Error {
+    fn into_error_object(
+        self,
+        message: impl Into<String>,
+        data: Option<String>,
+    ) -> ErrorObjectOwned {
+        ErrorObject::owned(self.into(), message.into(), data)
+    }
+
+    fn into_rpc_error(self, message: impl Into<String>, data: Option<String>) -> JsonRpseeError {
+        CallError::Custom(self.into_error_object(message, data)).into()
+    }
+}
+
+impl From<ApiError> for Error {
+    fn from(e: ApiError) -> Error {
+        match e {
+            ApiError::FailedToDecodeReturnValue { .. }
+            | ApiError::FailedToConvertReturnValue { .. }
+            | ApiError::FailedToConvertParameter { .. } => Error::DecodeError,
+            ApiError::UnknownBlock(_) => Error::UnknownBlock,
+            _ => Error::RuntimeError,
+        }
+    }
+}
+
+fn api_error(e: ApiError) -> JsonRpseeError {
+    let message = e.to_string();
+    let error = Error::from(e);
+    let message = match error {
+        Error::DecodeError => format!("Unable to decode runtime api call: {}", message),
+        Error::UnknownBlock => format!("Unknown block: {}", message),
+        _ => format!("Runtime api call failed: {}", message),
+    };
+    error.into_rpc_error(message, None)
+}
+
+impl<C, Block> TrustedAccounts<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+{
+    // Resolve the block to query, checking that it is known.
+    fn at_hash(&self, at: Option<Block::Hash>) -> RpcResult<Block::Hash> {
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        match self.client.header(at_hash) {
+            Ok(Some(_)) => Ok(at_hash),
+            Ok(None) => {
+                Err(Error::UnknownBlock
+                    .into_rpc_error(format!("Unknown block: {:?}", at_hash), None))
+            }
+            Err(e) => Err(Error::UnknownBlock.into_rpc_error(
+                format!("Unable to look up block: {:?}", at_hash),
+                Some(e.to_string()),
+            )),
+        }
+    }
+
+    // Check that the runtime implements at least `version` of the api.
+    fn ensure_api_version<AccountId>(&self, at_hash: Block::Hash, version: u32) -> RpcResult<()>
+    where
+        AccountId: Codec,
+        C::Api: TrustedAccountsRuntimeApi<Block, AccountId>,
+    {
+        let api_version = self
+            .client
+            .runtime_api()
+            .api_version::<dyn TrustedAccountsRuntimeApi<Block, AccountId>>(at_hash)
+            .map_err(api_error)?;
+        match api_version {
+            Some(api_version) if api_version >= version => Ok(()),
+            Some(api_version) => Err(Error::ApiUnavailable.into_rpc_error(
+                format!(
+                    "TrustedAccountsApi version {} is required, the runtime has version {}",
+                    version, api_version
+                ),
+                None,
+            )),
+            None => Err(Error::ApiUnavailable
+                .into_rpc_error("TrustedAccountsApi is not present in the runtime", None)),
         }
     }
 }
@@ -84,11 +322,12 @@ impl From<Error> for i32 {
 impl<C, AccountId, Block> TrustedAccountsApiServer<AccountId, <Block as BlockT>::Hash>
     for TrustedAccounts<C, Block>
 where
-    AccountId: Codec,
+    AccountId: Codec + Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
     Block: BlockT,
     C: Send + Sync + 'static,
     C: ProvideRuntimeApi<Block>,
     C: HeaderBackend<Block>,
+    C: BlockchainEvents<Block>,
     C::Api: TrustedAccountsRuntimeApi<Block, AccountId>,
 {
     fn is_trusted(
@@ -97,17 +336,13 @@ where
         trustee: AccountId,
         at: Option<<Block as BlockT>::Hash>,
     ) -> RpcResult<bool> {
-        let api = self.client.runtime_api();
-        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        let at_hash = self.at_hash(at)?;
+        self.ensure_api_version::<AccountId>(at_hash, 1)?;
 
-        api.is_trusted(at_hash, account, trustee).map_err(|e| {
-            CallError::Custom(ErrorObject::owned(
-                Error::RuntimeError.into(),
-                "Unable to query dispatch info.",
-                Some(e.to_string()),
-            ))
-            .into()
-        })
+        self.client
+            .runtime_api()
+            .is_trusted(at_hash, account, trustee)
+            .map_err(api_error)
     }
 
     fn is_trusted_only_deep(
@@ -116,18 +351,13 @@ where
         trustee: AccountId,
         at: Option<<Block as BlockT>::Hash>,
     ) -> RpcResult<bool> {
-        let api = self.client.runtime_api();
-        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        let at_hash = self.at_hash(at)?;
+        self.ensure_api_version::<AccountId>(at_hash, 1)?;
 
-        api.is_trusted_only_deep(at_hash, account, trustee)
-            .map_err(|e| {
-                CallError::Custom(ErrorObject::owned(
-                    Error::RuntimeError.into(),
-                    "Unable to query dispatch info.",
-                    Some(e.to_string()),
-                ))
-                .into()
-            })
+        self.client
+            .runtime_api()
+            .is_trusted_only_deep(at_hash, account, trustee)
+            .map_err(api_error)
     }
 
     fn is_trusted_deep(
@@ -136,17 +366,13 @@ where
         trustee: AccountId,
         at: Option<<Block as BlockT>::Hash>,
     ) -> RpcResult<bool> {
-        let api = self.client.runtime_api();
-        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        let at_hash = self.at_hash(at)?;
+        self.ensure_api_version::<AccountId>(at_hash, 1)?;
 
-        api.is_trusted_deep(at_hash, account, trustee).map_err(|e| {
-            CallError::Custom(ErrorObject::owned(
-                Error::RuntimeError.into(),
-                "Unable to query dispatch info.",
-                Some(e.to_string()),
-            ))
-            .into()
-        })
+        self.client
+            .runtime_api()
+            .is_trusted_deep(at_hash, account, trustee)
+            .map_err(api_error)
     }
 
     fn trusted_by(
@@ -154,16 +380,37 @@ where
         account: AccountId,
         at: Option<<Block as BlockT>::Hash>,
     ) -> RpcResult<Vec<AccountId>> {
-        let api = self.client.runtime_api();
-        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        let at_hash = self.at_hash(at)?;
+        self.ensure_api_version::<AccountId>(at_hash, 1)?;
 
-        api.trusted_by(at_hash, account).map_err(|e| {
-            CallError::Custom(ErrorObject::owned(
-                Error::RuntimeError.into(),
-                "Unable to query dispatch info.",
-                Some(e.to_string()),
-            ))
-            .into()
+        self.client
+            .runtime_api()
+            .trusted_by(at_hash, account)
+            .map_err(api_error)
+    }
+
+    fn trusted_by_paged(
+        &self,
+        account: AccountId,
+        cursor: Option<u32>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<TrustedByPage<AccountId>> {
+        let at_hash = self.at_hash(at)?;
+        self.ensure_api_version::<AccountId>(at_hash, 2)?;
+        let start = cursor.unwrap_or(0);
+
+        let (accounts, total) = self
+            .client
+            .runtime_api()
+            .trusted_by_paged(at_hash, account, start, limit)
+            .map_err(api_error)?;
+        let end = start.saturating_add(limit);
+
+        Ok(TrustedByPage {
+            accounts,
+            total,
+            next: if end < total { Some(end) } else { None },
         })
     }
 
@@ -173,17 +420,187 @@ where
         account_is_trusted_by_trusted: AccountId,
         at: Option<<Block as BlockT>::Hash>,
     ) -> RpcResult<Vec<AccountId>> {
-        let api = self.client.runtime_api();
-        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        let at_hash = self.at_hash(at)?;
+        self.ensure_api_version::<AccountId>(at_hash, 1)?;
+
+        self.client
+            .runtime_api()
+            .trusted_by_that_trust(at_hash, account, account_is_trusted_by_trusted)
+            .map_err(api_error)
+    }
+
+    fn trusting(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<AccountId>> {
+        let at_hash = self.at_hash(at)?;
+        self.ensure_api_version::<AccountId>(at_hash, 2)?;
+
+        self.client
+            .runtime_api()
+            .trusting(at_hash, account)
+            .map_err(api_error)
+    }
+
+    fn trusting_count(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<u32> {
+        let at_hash = self.at_hash(at)?;
+        self.ensure_api_version::<AccountId>(at_hash, 2)?;
+
+        self.client
+            .runtime_api()
+            .trusting_count(at_hash, account)
+            .map_err(api_error)
+    }
+
+    fn trust_path(
+        &self,
+        from: AccountId,
+        to: AccountId,
+        max_depth: u32,
+        max_nodes: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<Vec<AccountId>>> {
+        let at_hash = self.at_hash(at)?;
+        self.ensure_api_version::<AccountId>(at_hash, 2)?;
+        let max_depth = max_depth.min(self.max_trust_path_depth);
+        let max_nodes = max_nodes.min(self.max_trust_path_nodes);
+
+        self.client
+            .runtime_api()
+            .trust_path(at_hash, from, to, max_depth, max_nodes)
+            .map_err(api_error)
+    }
+
+    fn snapshot_page(
+        &self,
+        start: Option<AccountId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<SnapshotPage<AccountId, <Block as BlockT>::Hash>> {
+        let at_hash = self.at_hash(at)?;
+        self.ensure_api_version::<AccountId>(at_hash, 5)?;
+        let limit = limit.min(MAX_SNAPSHOT_PAGE_SIZE);
 
-        api.trusted_by_that_trust(at_hash, account, account_is_trusted_by_trusted)
-            .map_err(|e| {
-                CallError::Custom(ErrorObject::owned(
-                    Error::RuntimeError.into(),
-                    "Unable to query dispatch info.",
-                    Some(e.to_string()),
-                ))
-                .into()
-            })
+        let trusts = self
+            .client
+            .runtime_api()
+            .trust_graph(at_hash, start, limit)
+            .map_err(api_error)?;
+        let next = if (trusts.len() as u32) < limit {
+            None
+        } else {
+            trusts.last().map(|(truster, _)| truster.clone())
+        };
+
+        Ok(SnapshotPage {
+            block_hash: at_hash,
+            trusts,
+            next,
+        })
     }
+
+    fn subscribe_trust_changes(
+        &self,
+        mut sink: SubscriptionSink,
+        account: AccountId,
+    ) -> SubscriptionResult {
+        let executor = match &self.executor {
+            Some(executor) => executor.clone(),
+            None => {
+                let _ = sink.reject(
+                    Error::SubscriptionsUnavailable
+                        .into_error_object("Subscriptions are not enabled on this node", None),
+                );
+                return Ok(());
+            }
+        };
+        let client = self.client.clone();
+
+        // Each new best block, preceded by any blocks a re-org enacted below it.
+        let blocks = self
+            .client
+            .import_notification_stream()
+            .filter_map(|notification| {
+                future::ready(notification.is_new_best.then(|| {
+                    let mut blocks: Vec<_> = notification
+                        .tree_route
+                        .iter()
+                        .flat_map(|tree_route| tree_route.enacted().iter().map(|block| block.hash))
+                        .collect();
+                    blocks.push(notification.hash);
+                    blocks
+                }))
+            });
+        let changes = trust_change_stream(blocks, move |block_hash| {
+            let api = client.runtime_api();
+            // Blocks whose runtime predates the api have no changes to report.
+            match api
+                .api_version::<dyn TrustedAccountsRuntimeApi<Block, AccountId>>(block_hash)
+                .map_err(api_error)?
+            {
+                Some(version) if version >= 3 => api
+                    .trust_changes(block_hash, account.clone())
+                    .map_err(api_error),
+                _ => Ok(Vec::new()),
+            }
+        })
+        .boxed();
+
+        executor.spawn(
+            "trusted-accounts-trust-changes",
+            Some("rpc"),
+            async move {
+                // Close the subscription with the error rather than skip the block.
+                if let SubscriptionClosed::Failed(err) = sink.pipe_from_try_stream(changes).await {
+                    sink.close(err);
+                }
+            }
+            .boxed(),
+        );
+
+        Ok(())
+    }
+}
+
+// The trust changes in each batch of blocks, in order. No more blocks are queried after the first
+// error, as the changes of the remaining blocks could not be reported in order.
+fn trust_change_stream<AccountId, BlockHash, E>(
+    blocks: impl Stream<Item = Vec<BlockHash>>,
+    mut changes_at: impl FnMut(BlockHash) -> Result<Vec<(AccountId, AccountId, bool)>, E>,
+) -> impl Stream<Item = Result<TrustChange<AccountId, BlockHash>, E>>
+where
+    BlockHash: Copy,
+{
+    blocks
+        .flat_map(stream::iter)
+        .scan(false, move |failed, block_hash| {
+            if *failed {
+                return future::ready(None);
+            }
+            let result = changes_at(block_hash).map(|changes| (block_hash, changes));
+            *failed = result.is_err();
+            future::ready(Some(result))
+        })
+        .flat_map(|result| {
+            let changes: Vec<_> = match result {
+                Ok((block_hash, changes)) => changes
+                    .into_iter()
+                    .map(|(truster, trustee, trusted)| {
+                        Ok(TrustChange {
+                            block_hash,
+                            truster,
+                            trustee,
+                            trusted,
+                        })
+                    })
+                    .collect(),
+                Err(e) => vec![Err(e)],
+            };
+            stream::iter(changes)
+        })
 }
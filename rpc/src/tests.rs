@@ -0,0 +1,400 @@
+use crate::*;
+
+fn code(e: ApiError) -> i32 {
+    Error::from(e).into()
+}
+
+fn codec_error() -> codec::Error {
+    "invalid".into()
+}
+
+#[test]
+fn api_error_codes() {
+    assert_eq!(
+        code(ApiError::FailedToDecodeReturnValue {
+            function: "trusted_by",
+            error: codec_error(),
+        }),
+        2
+    );
+    assert_eq!(
+        code(ApiError::FailedToConvertReturnValue {
+            function: "trusted_by",
+            error: codec_error(),
+        }),
+        2
+    );
+    assert_eq!(
+        code(ApiError::FailedToConvertParameter {
+            function: "trusted_by",
+            parameter: "account",
+            error: codec_error(),
+        }),
+        2
+    );
+    assert_eq!(code(ApiError::UnknownBlock("0x00".into())), 3);
+    assert_eq!(code(ApiError::Application("trapped".into())), 1);
+    assert_eq!(code(ApiError::StateBackendIsNotTrie), 1);
+    assert_eq!(code(ApiError::UsingSameInstanceForDifferentBlocks), 1);
+}
+
+#[test]
+fn error_codes() {
+    assert_eq!(i32::from(Error::RuntimeError), 1);
+    assert_eq!(i32::from(Error::DecodeError), 2);
+    assert_eq!(i32::from(Error::UnknownBlock), 3);
+    assert_eq!(i32::from(Error::ApiUnavailable), 4);
+    assert_eq!(i32::from(Error::SubscriptionsUnavailable), 5);
+}
+
+#[test]
+fn api_error_message() {
+    let error = match api_error(ApiError::UnknownBlock("0x00".into())) {
+        JsonRpseeError::Call(CallError::Custom(error)) => error,
+        e => panic!("unexpected error: {}", e),
+    };
+    assert_eq!(error.code(), 3);
+    assert_eq!(
+        error.message(),
+        "Unknown block: Api called for an unknown Block: 0x00"
+    );
+
+    let error = match api_error(ApiError::Application("trapped".into())) {
+        JsonRpseeError::Call(CallError::Custom(error)) => error,
+        e => panic!("unexpected error: {}", e),
+    };
+    assert_eq!(error.code(), 1);
+    assert_eq!(error.message(), "Runtime api call failed: trapped");
+}
+
+#[test]
+fn trust_change_stream_ends_on_error() {
+    let blocks = stream::iter(vec![vec![1u8, 2], vec![3], vec![4]]);
+    let mut queried = Vec::new();
+    let changes = trust_change_stream(blocks, |block_hash| {
+        queried.push(block_hash);
+        match block_hash {
+            1 => Ok(vec![(10u8, 11u8, true), (10, 12, false)]),
+            2 => Ok(vec![]),
+            _ => Err("trapped"),
+        }
+    });
+    let changes: Vec<_> = futures::executor::block_on(changes.collect());
+
+    assert_eq!(
+        changes,
+        vec![
+            Ok(TrustChange {
+                block_hash: 1,
+                truster: 10,
+                trustee: 11,
+                trusted: true,
+            }),
+            Ok(TrustChange {
+                block_hash: 1,
+                truster: 10,
+                trustee: 12,
+                trusted: false,
+            }),
+            Err("trapped"),
+        ]
+    );
+    assert_eq!(queried, vec![1, 2, 3]);
+}
+
+type Block = sp_runtime::testing::Block<sp_runtime::testing::ExtrinsicWrapper<()>>;
+type Hash = sp_core::H256;
+
+fn best_hash() -> Hash {
+    Hash::repeat_byte(1)
+}
+
+// A runtime api that reports `version` of TrustedAccountsApi, or none, and answers the calls
+// from a fixed trust graph in which 1 trusts 2 and 2 trusts 3.
+struct MockApi {
+    version: Option<u32>,
+}
+
+impl MockApi {
+    fn call(&self, function: &str, params: Vec<u8>) -> Result<Vec<u8>, ApiError> {
+        use codec::{Decode, Encode};
+
+        let trusted_by = |account: u64| match account {
+            1 => vec![2u64],
+            2 => vec![3],
+            _ => vec![],
+        };
+        let is_trusted = |account: u64, trustee: u64| trusted_by(account).contains(&trustee);
+        let is_trusted_only_deep = |account: u64, trustee: u64| {
+            trusted_by(account)
+                .into_iter()
+                .any(|trusted| is_trusted(trusted, trustee))
+        };
+        let params = &mut &params[..];
+        Ok(match function {
+            "TrustedAccountsApi_is_trusted" => {
+                let (account, trustee) = <(u64, u64)>::decode(params).unwrap();
+                is_trusted(account, trustee).encode()
+            }
+            "TrustedAccountsApi_is_trusted_only_deep" => {
+                let (account, trustee) = <(u64, u64)>::decode(params).unwrap();
+                is_trusted_only_deep(account, trustee).encode()
+            }
+            "TrustedAccountsApi_is_trusted_deep" => {
+                let (account, trustee) = <(u64, u64)>::decode(params).unwrap();
+                (is_trusted(account, trustee) || is_trusted_only_deep(account, trustee)).encode()
+            }
+            "TrustedAccountsApi_trusted_by" => trusted_by(u64::decode(params).unwrap()).encode(),
+            "TrustedAccountsApi_trusted_by_that_trust" => {
+                let (account, trustee) = <(u64, u64)>::decode(params).unwrap();
+                trusted_by(account)
+                    .into_iter()
+                    .filter(|trusted| is_trusted(*trusted, trustee))
+                    .collect::<Vec<_>>()
+                    .encode()
+            }
+            "TrustedAccountsApi_trusting" => {
+                let account = u64::decode(params).unwrap();
+                (1..=3)
+                    .filter(|truster| is_trusted(*truster, account))
+                    .collect::<Vec<u64>>()
+                    .encode()
+            }
+            _ => {
+                return Err(ApiError::Application(
+                    format!("{} not mocked", function).into(),
+                ))
+            }
+        })
+    }
+}
+
+impl ApiExt<Block> for MockApi {
+    fn execute_in_transaction<F: FnOnce(&Self) -> sp_api::TransactionOutcome<R>, R>(
+        &self,
+        call: F,
+    ) -> R {
+        call(self).into_inner()
+    }
+
+    fn has_api<A: sp_api::RuntimeApiInfo + ?Sized>(&self, _: Hash) -> Result<bool, ApiError> {
+        Ok(self.version == Some(A::VERSION))
+    }
+
+    fn has_api_with<A: sp_api::RuntimeApiInfo + ?Sized, P: Fn(u32) -> bool>(
+        &self,
+        _: Hash,
+        pred: P,
+    ) -> Result<bool, ApiError> {
+        Ok(self.version.is_some_and(pred))
+    }
+
+    fn api_version<A: sp_api::RuntimeApiInfo + ?Sized>(
+        &self,
+        _: Hash,
+    ) -> Result<Option<u32>, ApiError> {
+        Ok(self.version)
+    }
+
+    fn record_proof(&mut self) {
+        unimplemented!()
+    }
+
+    fn extract_proof(&mut self) -> Option<sp_api::StorageProof> {
+        unimplemented!()
+    }
+
+    fn proof_recorder(&self) -> Option<sp_api::ProofRecorder<Block>> {
+        unimplemented!()
+    }
+
+    fn into_storage_changes<B: sp_state_machine::Backend<sp_runtime::traits::HashingFor<Block>>>(
+        &self,
+        _: &B,
+        _: Hash,
+    ) -> Result<sp_api::StorageChanges<Block>, String> {
+        unimplemented!()
+    }
+
+    fn set_call_context(&mut self, _: sp_api::CallContext) {
+        unimplemented!()
+    }
+
+    fn register_extension<E: sp_externalities::Extension>(&mut self, _: E) {
+        unimplemented!()
+    }
+}
+
+impl sp_api::Core<Block> for MockApi {
+    fn __runtime_api_internal_call_api_at(
+        &self,
+        _: Hash,
+        _: Vec<u8>,
+        _: &dyn Fn(sp_version::RuntimeVersion) -> &'static str,
+    ) -> Result<Vec<u8>, ApiError> {
+        unimplemented!()
+    }
+}
+
+impl TrustedAccountsRuntimeApi<Block, u64> for MockApi {
+    fn __runtime_api_internal_call_api_at(
+        &self,
+        _: Hash,
+        params: Vec<u8>,
+        fn_name: &dyn Fn(sp_version::RuntimeVersion) -> &'static str,
+    ) -> Result<Vec<u8>, ApiError> {
+        self.call(fn_name(Default::default()), params)
+    }
+}
+
+// A client that only knows the best block, running a runtime with `version` of the api.
+struct MockClient {
+    version: Option<u32>,
+}
+
+impl ProvideRuntimeApi<Block> for MockClient {
+    type Api = MockApi;
+
+    fn runtime_api(&self) -> sp_api::ApiRef<'_, Self::Api> {
+        MockApi {
+            version: self.version,
+        }
+        .into()
+    }
+}
+
+impl HeaderBackend<Block> for MockClient {
+    fn header(&self, hash: Hash) -> sp_blockchain::Result<Option<<Block as BlockT>::Header>> {
+        use sp_runtime::traits::Header;
+
+        Ok((hash == best_hash()).then(|| {
+            Header::new(
+                1,
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+            )
+        }))
+    }
+
+    fn info(&self) -> sp_blockchain::Info<Block> {
+        sp_blockchain::Info {
+            best_hash: best_hash(),
+            best_number: 1,
+            genesis_hash: Default::default(),
+            finalized_hash: Default::default(),
+            finalized_number: 0,
+            finalized_state: None,
+            number_leaves: 1,
+            block_gap: None,
+        }
+    }
+
+    fn status(&self, _: Hash) -> sp_blockchain::Result<sp_blockchain::BlockStatus> {
+        unimplemented!()
+    }
+
+    fn number(&self, _: Hash) -> sp_blockchain::Result<Option<u64>> {
+        unimplemented!()
+    }
+
+    fn hash(&self, _: u64) -> sp_blockchain::Result<Option<Hash>> {
+        unimplemented!()
+    }
+}
+
+impl BlockchainEvents<Block> for MockClient {
+    fn import_notification_stream(&self) -> sc_client_api::ImportNotifications<Block> {
+        unimplemented!()
+    }
+
+    fn every_import_notification_stream(&self) -> sc_client_api::ImportNotifications<Block> {
+        unimplemented!()
+    }
+
+    fn finality_notification_stream(&self) -> sc_client_api::FinalityNotifications<Block> {
+        unimplemented!()
+    }
+
+    fn storage_changes_notification_stream(
+        &self,
+        _: Option<&[sp_core::storage::StorageKey]>,
+        _: Option<
+            &[(
+                sp_core::storage::StorageKey,
+                Option<Vec<sp_core::storage::StorageKey>>,
+            )],
+        >,
+    ) -> sp_blockchain::Result<sc_client_api::StorageEventStream<Hash>> {
+        unimplemented!()
+    }
+}
+
+fn rpc(version: Option<u32>) -> TrustedAccounts<MockClient, Block> {
+    TrustedAccounts::new(Arc::new(MockClient { version }))
+}
+
+fn error_code(e: JsonRpseeError) -> i32 {
+    match e {
+        JsonRpseeError::Call(CallError::Custom(error)) => error.code(),
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn methods_call_the_runtime() {
+    let rpc = rpc(Some(1));
+    assert!(TrustedAccountsApiServer::<u64, Hash>::is_trusted(&rpc, 1, 2, None).unwrap());
+    assert!(!TrustedAccountsApiServer::<u64, Hash>::is_trusted(&rpc, 1, 3, None).unwrap());
+    assert!(TrustedAccountsApiServer::<u64, Hash>::is_trusted_only_deep(&rpc, 1, 3, None).unwrap());
+    assert!(
+        !TrustedAccountsApiServer::<u64, Hash>::is_trusted_only_deep(&rpc, 1, 2, None).unwrap()
+    );
+    assert!(TrustedAccountsApiServer::<u64, Hash>::is_trusted_deep(&rpc, 1, 2, None).unwrap());
+    assert_eq!(
+        TrustedAccountsApiServer::<u64, Hash>::trusted_by(&rpc, 1, Some(best_hash())).unwrap(),
+        vec![2]
+    );
+    assert_eq!(
+        TrustedAccountsApiServer::<u64, Hash>::trusted_by_that_trust(&rpc, 1, 3, None).unwrap(),
+        vec![2]
+    );
+
+    let rpc = self::rpc(Some(2));
+    assert_eq!(
+        TrustedAccountsApiServer::<u64, Hash>::trusting(&rpc, 3, None).unwrap(),
+        vec![2]
+    );
+}
+
+#[test]
+fn methods_require_the_api() {
+    let rpc = rpc(None);
+    let errors = [
+        TrustedAccountsApiServer::<u64, Hash>::is_trusted(&rpc, 1, 2, None).unwrap_err(),
+        TrustedAccountsApiServer::<u64, Hash>::is_trusted_only_deep(&rpc, 1, 3, None).unwrap_err(),
+        TrustedAccountsApiServer::<u64, Hash>::is_trusted_deep(&rpc, 1, 3, None).unwrap_err(),
+        TrustedAccountsApiServer::<u64, Hash>::trusted_by(&rpc, 1, None).unwrap_err(),
+        TrustedAccountsApiServer::<u64, Hash>::trusted_by_that_trust(&rpc, 1, 3, None).unwrap_err(),
+    ];
+    for error in errors {
+        assert_eq!(error_code(error), 4);
+    }
+
+    // Methods added in a later version need that version.
+    let rpc = self::rpc(Some(1));
+    assert_eq!(
+        error_code(TrustedAccountsApiServer::<u64, Hash>::trusting(&rpc, 3, None).unwrap_err()),
+        4
+    );
+}
+
+#[test]
+fn methods_require_a_known_block() {
+    let rpc = rpc(Some(1));
+    let error =
+        TrustedAccountsApiServer::<u64, Hash>::is_trusted(&rpc, 1, 2, Some(Hash::repeat_byte(2)))
+            .unwrap_err();
+    assert_eq!(error_code(error), 3);
+}
@@ -1,20 +1,395 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-acuity-trusted-accounts
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use crate::Pallet as TrustedAccounts;
+use frame_benchmarking::v2::*;
+use frame_support::{
+    sp_runtime::traits::Saturating,
+    traits::{
+        fungible::{Inspect, Mutate},
+        EnsureOrigin, Get,
+    },
+    weights::Weight,
+    BoundedVec,
+};
 use frame_system::RawOrigin;
+use sp_std::prelude::Vec;
 
-benchmarks! {
-    do_something {
-        let s in 0 .. 100;
+const SEED: u32 = 0;
+
+// Give an account enough funds to hold a deposit for the maximum number of trusted accounts.
+fn fund_account<T: Config>(who: &T::AccountId) {
+    let deposit = T::TrustDeposit::get().saturating_mul(T::MaxTrustedAccounts::get().into());
+    T::Currency::set_balance(who, T::Currency::minimum_balance().saturating_add(deposit));
+}
+
+// Make an account trust `n` other accounts.
+fn setup_trusted_accounts<T: Config>(truster: &T::AccountId, n: u32) {
+    for i in 0..n {
+        let trustee: T::AccountId = account("trustee", i, SEED);
+        TrustedAccounts::<T>::trust_account(RawOrigin::Signed(truster.clone()).into(), trustee)
+            .unwrap();
+    }
+}
+
+// Make another account trust `trustee`, so that an earlier truster is not last in the trustee's
+// reverse list and removing it has to move the last truster into its slot.
+fn add_trusting_account<T: Config>(trustee: &T::AccountId, i: u32) {
+    let truster: T::AccountId = account("truster", i, SEED);
+    fund_account::<T>(&truster);
+    TrustedAccounts::<T>::trust_account(RawOrigin::Signed(truster).into(), trustee.clone())
+        .unwrap();
+}
+
+// Fill the truster's list, starting with a trust pair that has expired but not been pruned and
+// that is not last in the trustee's reverse list. Returns the trustee of the expired pair.
+fn setup_expired_trust<T: Config>(truster: &T::AccountId) -> T::AccountId {
+    fund_account::<T>(truster);
+    let trustee: T::AccountId = account("trustee", u32::MAX, SEED);
+    let expires_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+    TrustedAccounts::<T>::trust_account_with_expiry(
+        RawOrigin::Signed(truster.clone()).into(),
+        trustee.clone(),
+        expires_at,
+    )
+    .unwrap();
+    add_trusting_account::<T>(&trustee, 0);
+    setup_trusted_accounts::<T>(truster, T::MaxTrustedAccounts::get() - 1);
+    frame_system::Pallet::<T>::set_block_number(expires_at);
+    trustee
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    // Benchmark `trust_account` with the list one short of full.
+    #[benchmark]
+    fn trust_account() {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        setup_trusted_accounts::<T>(&caller, T::MaxTrustedAccounts::get() - 1);
+        let trustee: T::AccountId = account("trustee", u32::MAX, SEED);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), trustee.clone());
+
+        assert!(TrustedAccounts::<T>::is_trusted(caller, trustee));
+    }
+
+    // Benchmark `trust_account` replacing an expired trust pair that is first in a full list and
+    // in the trustee's reverse list, so it has to be removed before the account is trusted again.
+    #[benchmark]
+    fn trust_account_replacing_expired() {
         let caller: T::AccountId = whitelisted_caller();
-    }: _(RawOrigin::Signed(caller), s)
-    verify {
-        assert_eq!(Something::<T>::get(), Some(s));
+        let trustee = setup_expired_trust::<T>(&caller);
+
+        #[block]
+        {
+            TrustedAccounts::<T>::trust_account(
+                RawOrigin::Signed(caller.clone()).into(),
+                trustee.clone(),
+            )
+            .unwrap();
+        }
+
+        assert!(TrustedAccounts::<T>::is_trusted(
+            caller.clone(),
+            trustee.clone()
+        ));
+        assert_eq!(
+            TrustedAccounts::<T>::account_trust_expiry(caller, trustee),
+            None
+        );
+    }
+
+    // Benchmark `untrust_account` removing the first account of a list of length `l`, so the
+    // last account has to be moved into its slot whenever `l > 1`. The caller is not last in the
+    // trustee's reverse list either.
+    #[benchmark]
+    fn untrust_account(l: Linear<1, { T::MaxTrustedAccounts::get() }>) {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        setup_trusted_accounts::<T>(&caller, l);
+        let trustee: T::AccountId = account("trustee", 0, SEED);
+        add_trusting_account::<T>(&trustee, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), trustee.clone());
+
+        assert!(!TrustedAccounts::<T>::is_trusted(
+            caller.clone(),
+            trustee.clone()
+        ));
+        assert_eq!(AccountTrustedAccountListCount::<T>::get(&caller), l - 1);
+        assert_eq!(
+            TrustedAccounts::<T>::trusting(trustee),
+            sp_std::vec![account("truster", 0, SEED)]
+        );
+    }
+
+    // Benchmark `trust_accounts` with a batch of `n` accounts.
+    #[benchmark]
+    fn trust_accounts(n: Linear<1, { T::MaxBatchSize::get().min(T::MaxTrustedAccounts::get()) }>) {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        let accounts: Vec<T::AccountId> = (0..n).map(|i| account("trustee", i, SEED)).collect();
+        let batch = BoundedVec::try_from(accounts).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), batch);
+
+        assert_eq!(AccountTrustedAccountListCount::<T>::get(&caller), n);
+    }
+
+    // Benchmark `untrust_accounts` removing `n` accounts from the front of a full list, so the
+    // last account has to be moved for each of them, in both the list and the reverse lists.
+    #[benchmark]
+    fn untrust_accounts(
+        n: Linear<1, { T::MaxBatchSize::get().min(T::MaxTrustedAccounts::get()) }>,
+    ) {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        setup_trusted_accounts::<T>(&caller, T::MaxTrustedAccounts::get());
+        let accounts: Vec<T::AccountId> = (0..n).map(|i| account("trustee", i, SEED)).collect();
+        for (i, trustee) in accounts.iter().enumerate() {
+            add_trusting_account::<T>(trustee, i as u32);
+        }
+        let batch = BoundedVec::try_from(accounts).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), batch);
+
+        assert_eq!(
+            AccountTrustedAccountListCount::<T>::get(&caller),
+            T::MaxTrustedAccounts::get() - n
+        );
+    }
+
+    // Benchmark `clear_trusted_accounts` removing `n` accounts.
+    #[benchmark]
+    fn clear_trusted_accounts(n: Linear<0, { T::MaxTrustedAccounts::get() }>) {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        setup_trusted_accounts::<T>(&caller, n);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), n);
+
+        assert_eq!(AccountTrustedAccountListCount::<T>::get(&caller), 0);
+    }
+
+    // Benchmark `update_trust_level`. Setting the default level removes the entry, which costs
+    // the same single write as inserting any other level.
+    #[benchmark]
+    fn update_trust_level() {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        setup_trusted_accounts::<T>(&caller, 1);
+        let trustee: T::AccountId = account("trustee", 0, SEED);
+        let level = T::TrustLevel::default();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), trustee.clone(), level);
+
+        assert_eq!(
+            TrustedAccounts::<T>::trust_level(caller, trustee),
+            Some(level)
+        );
+    }
+
+    // Benchmark `trust_account_with_expiry` with the list one short of full.
+    #[benchmark]
+    fn trust_account_with_expiry() {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        setup_trusted_accounts::<T>(&caller, T::MaxTrustedAccounts::get() - 1);
+        let trustee: T::AccountId = account("trustee", u32::MAX, SEED);
+        let expires_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            trustee.clone(),
+            expires_at,
+        );
+
+        assert_eq!(
+            TrustedAccounts::<T>::account_trust_expiry(caller, trustee),
+            Some(expires_at)
+        );
+    }
+
+    // Benchmark pruning a single expired trust pair that is first in a full list and in the
+    // trustee's reverse list, so the last accounts have to be moved into its slots.
+    #[benchmark]
+    fn expire_trust() {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        let trustee: T::AccountId = account("trustee", u32::MAX, SEED);
+        let expires_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+        TrustedAccounts::<T>::trust_account_with_expiry(
+            RawOrigin::Signed(caller.clone()).into(),
+            trustee.clone(),
+            expires_at,
+        )
+        .unwrap();
+        add_trusting_account::<T>(&trustee, 0);
+        setup_trusted_accounts::<T>(&caller, T::MaxTrustedAccounts::get() - 1);
+
+        #[block]
+        {
+            TrustedAccounts::<T>::prune_expired_trust(expires_at, Weight::MAX);
+        }
+
+        assert!(!AccountTrustedAccountIndex::<T>::contains_key(
+            &caller, &trustee
+        ));
+    }
+
+    // Benchmark `force_trust` with the list one short of full.
+    #[benchmark]
+    fn force_trust() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let truster: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&truster);
+        setup_trusted_accounts::<T>(&truster, T::MaxTrustedAccounts::get() - 1);
+        let trustee: T::AccountId = account("trustee", u32::MAX, SEED);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, truster.clone(), trustee.clone());
+
+        assert!(TrustedAccounts::<T>::is_trusted(truster, trustee));
+        Ok(())
+    }
+
+    // Benchmark `force_trust` replacing an expired trust pair, as for
+    // `trust_account_replacing_expired`.
+    #[benchmark]
+    fn force_trust_replacing_expired() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let truster: T::AccountId = whitelisted_caller();
+        let trustee = setup_expired_trust::<T>(&truster);
+
+        #[block]
+        {
+            TrustedAccounts::<T>::force_trust(origin, truster.clone(), trustee.clone()).unwrap();
+        }
+
+        assert!(TrustedAccounts::<T>::is_trusted(
+            truster.clone(),
+            trustee.clone()
+        ));
+        assert_eq!(
+            TrustedAccounts::<T>::account_trust_expiry(truster, trustee),
+            None
+        );
+        Ok(())
+    }
+
+    // Benchmark `force_untrust` removing the first account of a full list and the first truster
+    // of its reverse list, so the last accounts have to be moved into their slots.
+    #[benchmark]
+    fn force_untrust() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let truster: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&truster);
+        setup_trusted_accounts::<T>(&truster, T::MaxTrustedAccounts::get());
+        let trustee: T::AccountId = account("trustee", 0, SEED);
+        add_trusting_account::<T>(&trustee, 0);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, truster.clone(), trustee.clone());
+
+        assert!(!TrustedAccounts::<T>::is_trusted(truster, trustee));
+        Ok(())
+    }
+
+    // Benchmark `purge_trustee` removing `n` accounts that trust the same account, each of which
+    // trusts a full list of accounts so the last account has to be moved into its slot.
+    #[benchmark]
+    fn purge_trustee(n: Linear<0, MAX_PURGE_TRUSTEE_LIMIT>) -> Result<(), BenchmarkError> {
+        let origin =
+            T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let trustee: T::AccountId = account("trustee", u32::MAX, SEED);
+        for i in 0..n {
+            let truster: T::AccountId = account("truster", i, SEED);
+            fund_account::<T>(&truster);
+            TrustedAccounts::<T>::trust_account(
+                RawOrigin::Signed(truster.clone()).into(),
+                trustee.clone(),
+            )?;
+            setup_trusted_accounts::<T>(&truster, T::MaxTrustedAccounts::get() - 1);
+        }
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, trustee.clone(), n);
+
+        assert_eq!(AccountTrustingAccountListCount::<T>::get(&trustee), 0);
+        Ok(())
+    }
+
+    // Benchmark the trust score lookups made by `PrioritizeTrustedSender` for `n` anchors, all of
+    // which trust the sender so every lookup also reads the expiry. Runtimes should keep
+    // `MaxAnchors` within the benchmarked range.
+    #[benchmark]
+    fn trust_score(n: Linear<0, 100>) {
+        let sender: T::AccountId = whitelisted_caller();
+        let anchors: Vec<T::AccountId> = (0..n).map(|i| account("anchor", i, SEED)).collect();
+        for anchor in &anchors {
+            fund_account::<T>(anchor);
+            TrustedAccounts::<T>::trust_account(
+                RawOrigin::Signed(anchor.clone()).into(),
+                sender.clone(),
+            )
+            .unwrap();
+        }
+
+        #[block]
+        {
+            TrustedAccounts::<T>::trust_score(&sender, &anchors);
+        }
+
+        assert_eq!(TrustedAccounts::<T>::trust_score(&sender, &anchors), n);
+    }
+
+    // Benchmark `is_trusted_deep` for a truster that trusts `n` accounts, only the last of which
+    // trusts the trustee, so every trust pair of the truster is read.
+    #[benchmark]
+    fn is_trusted_deep(n: Linear<1, { T::MaxTrustedAccounts::get() }>) {
+        let truster: T::AccountId = account("truster", 0, SEED);
+        let intermediary: T::AccountId = account("intermediary", 0, SEED);
+        let trustee: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&truster);
+        fund_account::<T>(&intermediary);
+        setup_trusted_accounts::<T>(&truster, n - 1);
+        TrustedAccounts::<T>::trust_account(
+            RawOrigin::Signed(truster.clone()).into(),
+            intermediary.clone(),
+        )
+        .unwrap();
+        TrustedAccounts::<T>::trust_account(
+            RawOrigin::Signed(intermediary).into(),
+            trustee.clone(),
+        )
+        .unwrap();
+
+        #[block]
+        {
+            TrustedAccounts::<T>::is_trusted_deep(truster.clone(), trustee.clone());
+        }
+
+        assert!(TrustedAccounts::<T>::is_trusted_deep(truster, trustee));
     }
 
-    impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+    impl_benchmark_test_suite!(
+        TrustedAccounts,
+        crate::mock::new_test_ext(),
+        crate::mock::Test
+    );
 }
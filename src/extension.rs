@@ -0,0 +1,270 @@
+//! Signed extensions that take the trust graph into account when validating transactions.
+
+use crate::{Config, Pallet, WeightInfo};
+use codec::{Decode, Encode};
+use frame_support::{
+    dispatch::DispatchInfo,
+    sp_runtime::{
+        traits::{DispatchInfoOf, Dispatchable, SignedExtension},
+        transaction_validity::{
+            InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError,
+            ValidTransaction,
+        },
+    },
+    traits::{Contains, Get},
+    CloneNoBound, EqNoBound, PartialEqNoBound,
+};
+use scale_info::TypeInfo;
+use sp_std::{marker::PhantomData, prelude::Vec};
+
+/// The custom `InvalidTransaction` code for a sender that is not trusted by any trust root.
+pub const UNTRUSTED_SENDER: u8 = 1;
+
+/// Only allow transactions from the first `MaxRoots` of the `Roots` and the accounts they trust,
+/// directly or through an account they trust. Calls in `ExemptCalls` are allowed from any account.
+///
+/// Each root checked reads up to `MaxTrustedAccounts` trust pairs, so `pre_dispatch` registers
+/// `Pallet::is_trusted_deep_weight` for each of them as extra block weight.
+#[derive(Encode, Decode, CloneNoBound, EqNoBound, PartialEqNoBound, TypeInfo)]
+#[scale_info(skip_type_params(T, Roots, MaxRoots, ExemptCalls))]
+pub struct CheckTrustedSender<T, Roots, MaxRoots, ExemptCalls>(
+    PhantomData<(T, Roots, MaxRoots, ExemptCalls)>,
+)
+where
+    T: Config + Send + Sync,
+    Roots: Get<Vec<T::AccountId>> + Send + Sync + 'static,
+    MaxRoots: Get<u32> + Send + Sync + 'static,
+    ExemptCalls: Contains<<T as frame_system::Config>::RuntimeCall> + Send + Sync + 'static;
+
+impl<T, Roots, MaxRoots, ExemptCalls> CheckTrustedSender<T, Roots, MaxRoots, ExemptCalls>
+where
+    T: Config + Send + Sync,
+    Roots: Get<Vec<T::AccountId>> + Send + Sync + 'static,
+    MaxRoots: Get<u32> + Send + Sync + 'static,
+    ExemptCalls: Contains<<T as frame_system::Config>::RuntimeCall> + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    // The roots that are checked for calls that are not exempt.
+    fn roots() -> Vec<T::AccountId> {
+        let mut roots = Roots::get();
+        roots.truncate(MaxRoots::get() as usize);
+        roots
+    }
+
+    fn check(
+        who: &T::AccountId,
+        call: &<T as frame_system::Config>::RuntimeCall,
+    ) -> Result<(), TransactionValidityError> {
+        if ExemptCalls::contains(call) {
+            return Ok(());
+        }
+
+        let trusted = Self::roots()
+            .into_iter()
+            .any(|root| root == *who || Pallet::<T>::is_trusted_deep(root, who.clone()));
+        if !trusted {
+            return Err(InvalidTransaction::Custom(UNTRUSTED_SENDER).into());
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, Roots, MaxRoots, ExemptCalls> Default
+    for CheckTrustedSender<T, Roots, MaxRoots, ExemptCalls>
+where
+    T: Config + Send + Sync,
+    Roots: Get<Vec<T::AccountId>> + Send + Sync + 'static,
+    MaxRoots: Get<u32> + Send + Sync + 'static,
+    ExemptCalls: Contains<<T as frame_system::Config>::RuntimeCall> + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Roots, MaxRoots, ExemptCalls> sp_std::fmt::Debug
+    for CheckTrustedSender<T, Roots, MaxRoots, ExemptCalls>
+where
+    T: Config + Send + Sync,
+    Roots: Get<Vec<T::AccountId>> + Send + Sync + 'static,
+    MaxRoots: Get<u32> + Send + Sync + 'static,
+    ExemptCalls: Contains<<T as frame_system::Config>::RuntimeCall> + Send + Sync + 'static,
+{
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckTrustedSender")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T, Roots, MaxRoots, ExemptCalls> SignedExtension
+    for CheckTrustedSender<T, Roots, MaxRoots, ExemptCalls>
+where
+    T: Config + Send + Sync,
+    <T as frame_system::Config>::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+    Roots: Get<Vec<T::AccountId>> + Send + Sync + 'static,
+    MaxRoots: Get<u32> + Send + Sync + 'static,
+    ExemptCalls: Contains<<T as frame_system::Config>::RuntimeCall> + Send + Sync + 'static,
+{
+    const IDENTIFIER: &'static str = "CheckTrustedSender";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::RuntimeCall;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        Self::check(who, call)?;
+
+        Ok(ValidTransaction::default())
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> Result<(), TransactionValidityError> {
+        if !ExemptCalls::contains(call) {
+            // Charge for every root as if each were checked in the worst case.
+            let weight =
+                Pallet::<T>::is_trusted_deep_weight().saturating_mul(Self::roots().len() as u64);
+            frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, info.class);
+        }
+        Self::check(who, call)
+    }
+}
+
+/// Raise the priority of transactions by `Boost` for each of the first `MaxAnchors` of the
+/// `Anchors` that directly trusts the sender.
+///
+/// The lookups only run in `validate`, when the transaction pool checks the transaction, and
+/// are bounded by `MaxAnchors`. Their cost is `WeightInfo::trust_score` for the anchors checked,
+/// which `pre_dispatch` registers as extra block weight.
+#[derive(Encode, Decode, CloneNoBound, EqNoBound, PartialEqNoBound, TypeInfo)]
+#[scale_info(skip_type_params(T, Anchors, MaxAnchors, Boost))]
+pub struct PrioritizeTrustedSender<T, Anchors, MaxAnchors, Boost>(
+    PhantomData<(T, Anchors, MaxAnchors, Boost)>,
+)
+where
+    T: Config + Send + Sync,
+    Anchors: Get<Vec<T::AccountId>> + Send + Sync + 'static,
+    MaxAnchors: Get<u32> + Send + Sync + 'static,
+    Boost: Get<TransactionPriority> + Send + Sync + 'static;
+
+impl<T, Anchors, MaxAnchors, Boost> PrioritizeTrustedSender<T, Anchors, MaxAnchors, Boost>
+where
+    T: Config + Send + Sync,
+    Anchors: Get<Vec<T::AccountId>> + Send + Sync + 'static,
+    MaxAnchors: Get<u32> + Send + Sync + 'static,
+    Boost: Get<TransactionPriority> + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    // The anchors that the sender's trust is looked up from.
+    fn anchors() -> Vec<T::AccountId> {
+        let mut anchors = Anchors::get();
+        anchors.truncate(MaxAnchors::get() as usize);
+        anchors
+    }
+}
+
+impl<T, Anchors, MaxAnchors, Boost> Default
+    for PrioritizeTrustedSender<T, Anchors, MaxAnchors, Boost>
+where
+    T: Config + Send + Sync,
+    Anchors: Get<Vec<T::AccountId>> + Send + Sync + 'static,
+    MaxAnchors: Get<u32> + Send + Sync + 'static,
+    Boost: Get<TransactionPriority> + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Anchors, MaxAnchors, Boost> sp_std::fmt::Debug
+    for PrioritizeTrustedSender<T, Anchors, MaxAnchors, Boost>
+where
+    T: Config + Send + Sync,
+    Anchors: Get<Vec<T::AccountId>> + Send + Sync + 'static,
+    MaxAnchors: Get<u32> + Send + Sync + 'static,
+    Boost: Get<TransactionPriority> + Send + Sync + 'static,
+{
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "PrioritizeTrustedSender")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T, Anchors, MaxAnchors, Boost> SignedExtension
+    for PrioritizeTrustedSender<T, Anchors, MaxAnchors, Boost>
+where
+    T: Config + Send + Sync,
+    <T as frame_system::Config>::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+    Anchors: Get<Vec<T::AccountId>> + Send + Sync + 'static,
+    MaxAnchors: Get<u32> + Send + Sync + 'static,
+    Boost: Get<TransactionPriority> + Send + Sync + 'static,
+{
+    const IDENTIFIER: &'static str = "PrioritizeTrustedSender";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::RuntimeCall;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        _call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        let score = Pallet::<T>::trust_score(who, &Self::anchors());
+
+        Ok(ValidTransaction {
+            priority: TransactionPriority::from(score).saturating_mul(Boost::get()),
+            ..Default::default()
+        })
+    }
+
+    fn pre_dispatch(
+        self,
+        _who: &Self::AccountId,
+        _call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> Result<(), TransactionValidityError> {
+        // Charge for looking up every anchor, as `validate` did.
+        let weight = T::WeightInfo::trust_score(Self::anchors().len() as u32);
+        frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, info.class);
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+//! Searches of the trust graph, shared by the pallet and off-chain indexes of the graph.
+
+use sp_std::{
+    collections::{btree_map::BTreeMap, vec_deque::VecDeque},
+    prelude::Vec,
+    vec,
+};
+
+/// The shortest chain of trust from `from` to `to`, including both, searching at most
+/// `max_depth` hops and expanding at most `max_nodes` accounts. `trusted_by` gives the accounts
+/// that an account trusts.
+pub fn trust_path<AccountId, E>(
+    from: AccountId,
+    to: AccountId,
+    max_depth: u32,
+    max_nodes: u32,
+    mut trusted_by: impl FnMut(&AccountId) -> Result<Vec<AccountId>, E>,
+) -> Result<Option<Vec<AccountId>>, E>
+where
+    AccountId: Ord + Clone,
+{
+    if from == to {
+        return Ok(Some(vec![from]));
+    }
+    // Breadth-first search, so the first path found is the shortest.
+    let mut parents = BTreeMap::new();
+    let mut queue = VecDeque::new();
+    queue.push_back((from.clone(), 0));
+    let mut expanded = 0;
+
+    while let Some((account, depth)) = queue.pop_front() {
+        if depth >= max_depth {
+            continue;
+        }
+        if expanded >= max_nodes {
+            break;
+        }
+        expanded += 1;
+
+        for account_trusted in trusted_by(&account)? {
+            if account_trusted == from || parents.contains_key(&account_trusted) {
+                continue;
+            }
+            parents.insert(account_trusted.clone(), account.clone());
+
+            if account_trusted == to {
+                // Walk back up the tree to build the path.
+                let mut path = vec![to.clone()];
+                let mut current = to;
+                while let Some(parent) = parents.get(&current) {
+                    path.push(parent.clone());
+                    current = parent.clone();
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+
+            queue.push_back((account_trusted, depth + 1));
+        }
+    }
+
+    Ok(None)
+}
@@ -14,48 +14,215 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-#[frame_support::pallet(dev_mode)]
+pub mod extension;
+pub mod graph;
+pub mod migrations;
+pub mod origin;
+pub mod traits;
+pub mod weights;
+pub use extension::{CheckTrustedSender, PrioritizeTrustedSender};
+pub use origin::{EnsureTrustedBy, EnsureTrustedDeepBy};
+pub use traits::TrustProvider;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
 pub mod pallet {
-    use frame_support::pallet_prelude::*;
+    #[cfg(any(feature = "try-runtime", test))]
+    use frame_support::sp_runtime::TryRuntimeError;
+    use frame_support::{
+        pallet_prelude::*,
+        sp_runtime::traits::{One, Zero},
+        traits::{
+            fungible::{Inspect, Mutate, MutateHold},
+            tokens::Precision,
+            OnKilledAccount,
+        },
+    };
     use frame_system::pallet_prelude::*;
 
+    use crate::WeightInfo;
+
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+    /// The most trust pairs `purge_trustee` removes in one call, which its weight is
+    /// benchmarked up to.
+    pub const MAX_PURGE_TRUSTEE_LIMIT: u32 = 100;
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
+    /// A reason for the pallet placing a hold on funds.
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// Funds are held as a deposit for trusting an account.
+        TrustDeposit,
+    }
+
     /// Configure the pallet by specifying the parameters and types on which it depends.
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// Because this pallet emits events, it depends on the runtime's definition of an event.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// The maximum number of accounts that an account can trust.
+        #[pallet::constant]
+        type MaxTrustedAccounts: Get<u32>;
+
+        /// The currency used to hold trust deposits.
+        type Currency: Mutate<Self::AccountId>
+            + MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// The overarching hold reason.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// The amount held from the truster for each account that it trusts.
+        #[pallet::constant]
+        type TrustDeposit: Get<BalanceOf<Self>>;
+
+        /// The level of trust an account can place in another. Newly trusted accounts get the
+        /// default level.
+        type TrustLevel: Parameter + Member + MaxEncodedLen + Copy + Default + Ord;
+
+        /// The maximum number of accounts that can be trusted or untrusted in a single call.
+        #[pallet::constant]
+        type MaxBatchSize: Get<u32>;
+
+        /// The origin that can trust and untrust on behalf of any account.
+        type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::storage]
     #[pallet::getter(fn account_trusted_account_list_count)]
     // Mapping of account to count of accounts that it trusts.
     pub type AccountTrustedAccountListCount<T: Config> =
-        StorageMap<_, Identity, T::AccountId, u32, ValueQuery>;
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn account_trusted_account_list)]
     // Mapping of account to array of trusted accounts.
     pub type AccountTrustedAccountList<T: Config> =
-        StorageDoubleMap<_, Identity, T::AccountId, Twox64Concat, u32, T::AccountId>;
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, u32, T::AccountId>;
 
     #[pallet::storage]
     #[pallet::getter(fn account_trusted_account_index)]
     // Mapping of account1 to mapping of account2 to index + 1 in AccountTrustedAccountList.
     pub type AccountTrustedAccountIndex<T: Config> =
-        StorageDoubleMap<_, Identity, T::AccountId, Blake2_128Concat, T::AccountId, u32>;
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, u32>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn account_trusting_account_list_count)]
+    // Mapping of account to count of accounts that trust it.
+    pub type AccountTrustingAccountListCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn account_trusting_account_list)]
+    // Mapping of account to array of accounts that trust it.
+    pub type AccountTrustingAccountList<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, u32, T::AccountId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn account_trusting_account_index)]
+    // Mapping of trustee to mapping of truster to index + 1 in AccountTrustingAccountList.
+    pub type AccountTrustingAccountIndex<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, u32>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn account_trust_level)]
+    // Mapping of account1 to mapping of account2 to trust level, if not the default.
+    pub type AccountTrustLevel<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        T::TrustLevel,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn account_trust_expiry)]
+    // Mapping of account1 to mapping of account2 to the block at which the trust expires.
+    pub type AccountTrustExpiry<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BlockNumberFor<T>,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn account_trust_deposit)]
+    // Mapping of account1 to mapping of account2 to the deposit held for the trust pair, if any.
+    pub type AccountTrustDeposit<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    // Mapping of block number to the trust pairs that expire at that block.
+    pub type TrustExpiryQueue<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Blake2_128Concat,
+        (T::AccountId, T::AccountId),
+        (),
+    >;
+
+    #[pallet::storage]
+    // The next block in TrustExpiryQueue that has not been fully pruned.
+    pub type NextTrustExpiryBlock<T: Config> = StorageValue<_, BlockNumberFor<T>>;
+
+    #[pallet::storage]
+    // Reaped accounts whose outgoing trust has not been fully cleared yet.
+    pub type KilledAccountQueue<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+    #[pallet::storage]
+    // The stage reached by a storage migration that is still running.
+    pub type MigrationInProgress<T: Config> =
+        StorageValue<_, crate::migrations::v1::MigrationStage<T::AccountId>>;
 
     // Pallets use events to inform users when important changes are made.
     // https://docs.substrate.io/v3/runtime/events-and-errors
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// An account has trusted another. [truster, trustee]
-        AccountTrusted(T::AccountId, T::AccountId),
+        /// An account has trusted another. [truster, trustee, level]
+        AccountTrusted(T::AccountId, T::AccountId, T::TrustLevel),
         /// An account has untrusted another. [truster, trustee]
         AccountUntrusted(T::AccountId, T::AccountId),
+        /// A trust deposit has been held. [truster, amount]
+        TrustDepositHeld(T::AccountId, BalanceOf<T>),
+        /// A trust deposit has been released. [truster, amount]
+        TrustDepositReleased(T::AccountId, BalanceOf<T>),
+        /// An account has untrusted some of its trusted accounts. [truster, removed, more_remaining]
+        TrustedAccountsCleared(T::AccountId, u32, bool),
+        /// An account has changed its level of trust in another. [truster, trustee, level]
+        TrustLevelUpdated(T::AccountId, T::AccountId, T::TrustLevel),
+        /// An account's trust in another has expired. [truster, trustee]
+        TrustExpired(T::AccountId, T::AccountId),
+        /// An account has been made to trust another by the force origin. [truster, trustee]
+        AccountForceTrusted(T::AccountId, T::AccountId),
+        /// An account has been made to untrust another by the force origin. [truster, trustee]
+        AccountForceUntrusted(T::AccountId, T::AccountId),
+        /// Some of the accounts trusting an account have been made to untrust it by the force
+        /// origin. [trustee, removed, more_remaining]
+        TrustingAccountsPurged(T::AccountId, u32, bool),
     }
 
     // Errors inform users that something went wrong.
@@ -67,6 +234,60 @@ pub mod pallet {
         AlreadyTrusted,
         /// The account is not trusted.
         NotTrusted,
+        /// The account already trusts the maximum number of accounts.
+        TooManyTrustedAccounts,
+        /// The expiry block has already passed.
+        ExpiryInPast,
+        /// A storage migration is in progress.
+        MigrationInProgress,
+    }
+
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Trust pairs to create at genesis. [truster, trustee]
+        ///
+        /// No deposits are held for these.
+        pub trusts: sp_std::prelude::Vec<(T::AccountId, T::AccountId)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (truster, trustee) in &self.trusts {
+                assert!(truster != trustee, "Genesis trust pair trusts self");
+                assert!(
+                    !<AccountTrustedAccountIndex<T>>::contains_key(truster, trustee),
+                    "Duplicate genesis trust pair"
+                );
+                let count = <AccountTrustedAccountListCount<T>>::get(truster);
+                assert!(
+                    count < T::MaxTrustedAccounts::get(),
+                    "Genesis trust pairs exceed MaxTrustedAccounts"
+                );
+                Pallet::<T>::insert_trust(truster, trustee, count);
+            }
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            // Finish any storage migration before pruning expired trust.
+            if <MigrationInProgress<T>>::exists() {
+                return crate::migrations::v1::step::<T>(remaining_weight);
+            }
+            let mut used = T::DbWeight::get().reads(1);
+            used = used.saturating_add(Self::clear_killed_accounts(
+                remaining_weight.saturating_sub(used),
+            ));
+            Self::prune_expired_trust(n, remaining_weight.saturating_sub(used)).saturating_add(used)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+            Self::do_try_state()
+        }
     }
 
     // Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -75,44 +296,313 @@ pub mod pallet {
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(
+            T::WeightInfo::trust_account().max(T::WeightInfo::trust_account_replacing_expired())
+        )]
         pub fn trust_account(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
             // Check that the extrinsic was signed and get the signer.
             // This function will return an error if the extrinsic is not signed.
             // https://docs.substrate.io/v3/runtime/origins
             let sender = ensure_signed(origin)?;
+            Self::do_trust_account(sender, account, T::TrustLevel::default(), None)
+        }
+
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::untrust_account(T::MaxTrustedAccounts::get()))]
+        pub fn untrust_account(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+            // Check that the extrinsic was signed and get the signer.
+            // This function will return an error if the extrinsic is not signed.
+            // https://docs.substrate.io/v3/runtime/origins
+            let sender = ensure_signed(origin)?;
+            Self::do_untrust_account(sender, account)
+        }
+
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+            T::WeightInfo::trust_accounts(accounts.len() as u32).saturating_add(
+                Pallet::<T>::replace_expired_weight().saturating_mul(accounts.len() as u64)
+            )
+        )]
+        pub fn trust_accounts(
+            origin: OriginFor<T>,
+            accounts: BoundedVec<T::AccountId, T::MaxBatchSize>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // If any account cannot be trusted the whole call is reverted.
+            for account in accounts {
+                Self::do_trust_account(sender.clone(), account, T::TrustLevel::default(), None)?;
+            }
+            Ok(())
+        }
+
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::untrust_accounts(accounts.len() as u32))]
+        pub fn untrust_accounts(
+            origin: OriginFor<T>,
+            accounts: BoundedVec<T::AccountId, T::MaxBatchSize>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            // If any account cannot be untrusted the whole call is reverted.
+            for account in accounts {
+                Self::do_untrust_account(sender.clone(), account)?;
+            }
+            Ok(())
+        }
+
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::clear_trusted_accounts(
+            (*limit).min(T::MaxTrustedAccounts::get())
+        ))]
+        #[allow(clippy::useless_conversion)]
+        pub fn clear_trusted_accounts(
+            origin: OriginFor<T>,
+            limit: u32,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_not_migrating()?;
+            // Get the total number of accounts the sender trusts.
+            let count = <AccountTrustedAccountListCount<T>>::get(&sender);
+            // Never remove more than the weight was charged for.
+            let removed = count.min(limit).min(T::MaxTrustedAccounts::get());
+
+            //----------------------------------------
+
+            // Untrust from the end of the list so no accounts have to be moved.
+            for i in (count - removed..count).rev() {
+                let account = <AccountTrustedAccountList<T>>::get(&sender, i).unwrap();
+                Self::do_untrust_account(sender.clone(), account)?;
+            }
+            // Emit the event.
+            Self::deposit_event(Event::TrustedAccountsCleared(
+                sender,
+                removed,
+                removed < count,
+            ));
+            // Only charge for the accounts actually removed.
+            Ok(Some(T::WeightInfo::clear_trusted_accounts(removed)).into())
+        }
+
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::update_trust_level())]
+        pub fn update_trust_level(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            level: T::TrustLevel,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_not_migrating()?;
+            // Check that the account is trusted.
+            if !<AccountTrustedAccountIndex<T>>::contains_key(&sender, &account) {
+                Err(Error::<T>::NotTrusted)?;
+            }
+
+            //----------------------------------------
+
+            // Store the level, only using storage if it is not the default.
+            Self::set_trust_level(&sender, &account, level);
+            // Emit the event.
+            Self::deposit_event(Event::TrustLevelUpdated(sender, account, level));
+            Ok(())
+        }
+
+        #[pallet::call_index(6)]
+        #[pallet::weight(
+            T::WeightInfo::trust_account_with_expiry()
+                .saturating_add(Pallet::<T>::replace_expired_weight())
+        )]
+        pub fn trust_account_with_expiry(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            expires_at: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::do_trust_account(sender, account, T::TrustLevel::default(), Some(expires_at))
+        }
+
+        /// Make `truster` trust `trustee`. No deposit is held for trust created this way.
+        #[pallet::call_index(7)]
+        #[pallet::weight(
+            T::WeightInfo::force_trust().max(T::WeightInfo::force_trust_replacing_expired())
+        )]
+        pub fn force_trust(
+            origin: OriginFor<T>,
+            truster: T::AccountId,
+            trustee: T::AccountId,
+        ) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+            let count = Self::ensure_can_trust(&truster, &trustee, None)?;
+
+            //----------------------------------------
+
+            // Add the trust pair to the list and reverse list.
+            Self::insert_trust(&truster, &trustee, count);
+            // Emit the event.
+            Self::deposit_event(Event::AccountForceTrusted(truster, trustee));
+            Ok(())
+        }
+
+        /// Make `truster` untrust `trustee`, releasing any deposit held for the pair.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::force_untrust())]
+        pub fn force_untrust(
+            origin: OriginFor<T>,
+            truster: T::AccountId,
+            trustee: T::AccountId,
+        ) -> DispatchResult {
+            T::ForceOrigin::ensure_origin(origin)?;
+            Self::ensure_not_migrating()?;
+            // Get the index + 1 of the account to be removed
+            let i = match <AccountTrustedAccountIndex<T>>::get(&truster, &trustee) {
+                Some(i) => i,
+                None => return Err(Error::<T>::NotTrusted.into()),
+            };
+
+            //----------------------------------------
+
+            Self::remove_trust(&truster, &trustee, i)?;
+            // Emit the event.
+            Self::deposit_event(Event::AccountForceUntrusted(truster, trustee));
+            Ok(())
+        }
+
+        /// Make up to `limit` of the accounts that trust `account` untrust it. Call again to
+        /// continue if `more_remaining` is set in the event. `limit` is capped at
+        /// `MAX_PURGE_TRUSTEE_LIMIT`.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::purge_trustee((*limit).min(MAX_PURGE_TRUSTEE_LIMIT)))]
+        #[allow(clippy::useless_conversion)]
+        pub fn purge_trustee(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            limit: u32,
+        ) -> DispatchResultWithPostInfo {
+            T::ForceOrigin::ensure_origin(origin)?;
+            Self::ensure_not_migrating()?;
+            // Get the total number of accounts that trust the account.
+            let count = <AccountTrustingAccountListCount<T>>::get(&account);
+            // Never remove more than the weight was charged for.
+            let removed = count.min(limit).min(MAX_PURGE_TRUSTEE_LIMIT);
+
+            //----------------------------------------
+
+            // Untrust from the end of the reverse list so no trusters have to be moved in it.
+            for i in (count - removed..count).rev() {
+                let truster = <AccountTrustingAccountList<T>>::get(&account, i).unwrap();
+                let j = <AccountTrustedAccountIndex<T>>::get(&truster, &account).unwrap();
+                Self::remove_trust(&truster, &account, j)?;
+                Self::deposit_event(Event::AccountForceUntrusted(truster, account.clone()));
+            }
+            // Emit the event.
+            Self::deposit_event(Event::TrustingAccountsPurged(
+                account,
+                removed,
+                removed < count,
+            ));
+            // Only charge for the trust pairs actually removed.
+            Ok(Some(T::WeightInfo::purge_trustee(removed)).into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        // Check that the trust lists are not part way through a storage migration.
+        fn ensure_not_migrating() -> DispatchResult {
+            if <MigrationInProgress<T>>::exists() {
+                Err(Error::<T>::MigrationInProgress)?;
+            }
+            Ok(())
+        }
+
+        fn set_trust_level(sender: &T::AccountId, account: &T::AccountId, level: T::TrustLevel) {
+            if level == T::TrustLevel::default() {
+                <AccountTrustLevel<T>>::remove(sender, account);
+            } else {
+                <AccountTrustLevel<T>>::insert(sender, account, level);
+            }
+        }
+
+        // The extra weight of replacing an expired trust pair when trusting an account. Adding it
+        // to the weight of trusting a new account gives the larger of the two.
+        fn replace_expired_weight() -> Weight {
+            T::WeightInfo::trust_account_replacing_expired()
+                .saturating_sub(T::WeightInfo::trust_account())
+        }
+
+        // Check that the sender can trust the account, removing an expired trust pair that is
+        // being replaced. Returns the number of accounts the sender already trusts. Callers must
+        // charge for the replacement as well as for trusting a new account.
+        fn ensure_can_trust(
+            sender: &T::AccountId,
+            account: &T::AccountId,
+            expires_at: Option<BlockNumberFor<T>>,
+        ) -> Result<u32, DispatchError> {
+            Self::ensure_not_migrating()?;
             // Check that the sender is not attempting to trust themselves.
             if sender == account {
                 Err(Error::<T>::TrustSelf)?;
             }
+            // Check that the expiry is in the future.
+            let now = <frame_system::Pallet<T>>::block_number();
+            if let Some(expires_at) = expires_at {
+                if expires_at <= now {
+                    Err(Error::<T>::ExpiryInPast)?;
+                }
+            }
             // Check that the account is not already trusted.
-            if <AccountTrustedAccountIndex<T>>::contains_key(&sender, &account) {
-                Err(Error::<T>::AlreadyTrusted)?;
+            if let Some(i) = <AccountTrustedAccountIndex<T>>::get(sender, account) {
+                // Trust that has expired but not been pruned yet can be replaced.
+                if !Self::is_expired(sender, account, now) {
+                    Err(Error::<T>::AlreadyTrusted)?;
+                }
+                Self::remove_trust(sender, account, i)?;
+                Self::deposit_event(Event::TrustExpired(sender.clone(), account.clone()));
             }
             // Get the total number of accounts the sender already trusts.
-            let count = <AccountTrustedAccountListCount<T>>::get(&sender);
+            let count = <AccountTrustedAccountListCount<T>>::get(sender);
+            // Check that the sender has not reached the limit of trusted accounts.
+            if count >= T::MaxTrustedAccounts::get() {
+                Err(Error::<T>::TooManyTrustedAccounts)?;
+            }
+            Ok(count)
+        }
+
+        fn do_trust_account(
+            sender: T::AccountId,
+            account: T::AccountId,
+            level: T::TrustLevel,
+            expires_at: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let count = Self::ensure_can_trust(&sender, &account, expires_at)?;
 
             //----------------------------------------
 
-            // Insert the new account at the end of the list.
-            <AccountTrustedAccountList<T>>::insert(&sender, count, &account);
-            // Update the size of the list.
-            <AccountTrustedAccountListCount<T>>::insert(&sender, count + 1);
-            // Store index + 1 for this trust pair.
-            <AccountTrustedAccountIndex<T>>::insert(&sender, &account, count + 1);
-            // Emit the event.
-            Self::deposit_event(Event::AccountTrusted(sender, account));
-            // Return a successful DispatchResultWithPostInfo
+            // Hold the deposit for this trust pair and record it, so exactly this amount is
+            // released when the pair is removed.
+            let deposit = T::TrustDeposit::get();
+            T::Currency::hold(&HoldReason::TrustDeposit.into(), &sender, deposit)?;
+            if !deposit.is_zero() {
+                <AccountTrustDeposit<T>>::insert(&sender, &account, deposit);
+            }
+            // Add the trust pair to the list and reverse list.
+            Self::insert_trust(&sender, &account, count);
+            // Store the trust level.
+            Self::set_trust_level(&sender, &account, level);
+            // Store the expiry and queue the trust pair for pruning.
+            if let Some(expires_at) = expires_at {
+                <AccountTrustExpiry<T>>::insert(&sender, &account, expires_at);
+                <TrustExpiryQueue<T>>::insert(expires_at, (&sender, &account), ());
+                <NextTrustExpiryBlock<T>>::mutate(|next| match next {
+                    Some(next) if *next <= expires_at => {}
+                    _ => *next = Some(expires_at),
+                });
+            }
+            // Emit the events.
+            Self::deposit_event(Event::TrustDepositHeld(sender.clone(), deposit));
+            Self::deposit_event(Event::AccountTrusted(sender, account, level));
             Ok(())
         }
 
-        #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
-        pub fn untrust_account(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
-            // Check that the extrinsic was signed and get the signer.
-            // This function will return an error if the extrinsic is not signed.
-            // https://docs.substrate.io/v3/runtime/origins
-            let sender = ensure_signed(origin)?;
+        fn do_untrust_account(sender: T::AccountId, account: T::AccountId) -> DispatchResult {
+            Self::ensure_not_migrating()?;
             // Get the index + 1 of the account to be removed
             let i = match <AccountTrustedAccountIndex<T>>::get(&sender, &account) {
                 Some(i) => i,
@@ -121,43 +611,376 @@ pub mod pallet {
 
             //----------------------------------------
 
+            Self::remove_trust(&sender, &account, i)?;
+            // Emit the event.
+            Self::deposit_event(Event::AccountUntrusted(sender, account));
+            Ok(())
+        }
+
+        fn is_expired(
+            account: &T::AccountId,
+            trustee: &T::AccountId,
+            now: BlockNumberFor<T>,
+        ) -> bool {
+            match <AccountTrustExpiry<T>>::get(account, trustee) {
+                Some(expires_at) => expires_at <= now,
+                None => false,
+            }
+        }
+
+        // Remove expired trust pairs, stepping through TrustExpiryQueue one block at a time
+        // until it catches up with the current block or the weight runs out. The cursor is
+        // cleared once the queue is empty, and set again when trust with an expiry is added.
+        pub(crate) fn prune_expired_trust(
+            now: BlockNumberFor<T>,
+            remaining_weight: Weight,
+        ) -> Weight {
+            let read = T::DbWeight::get().reads(1);
+            let mut block = match <NextTrustExpiryBlock<T>>::get() {
+                Some(block) => block,
+                None => return read,
+            };
+            // Reserve the weight of checking the queue and storing the cursor at the end.
+            let mut used = read.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+            if used.any_gt(remaining_weight) {
+                return read;
+            }
+            let step = T::DbWeight::get().reads_writes(1, 1);
+            let per_pair = T::WeightInfo::expire_trust();
+
+            while block <= now {
+                if used.saturating_add(step).any_gt(remaining_weight) {
+                    break;
+                }
+                used = used.saturating_add(step);
+                let mut pairs = <TrustExpiryQueue<T>>::iter_key_prefix(block);
+                let mut finished = true;
+                loop {
+                    if used.saturating_add(per_pair).any_gt(remaining_weight) {
+                        finished = false;
+                        break;
+                    }
+                    let (truster, trustee) = match pairs.next() {
+                        Some(pair) => pair,
+                        None => break,
+                    };
+                    used = used.saturating_add(per_pair);
+                    <TrustExpiryQueue<T>>::remove(block, (&truster, &trustee));
+                    // The pair may have been untrusted or trusted again since it was queued.
+                    if <AccountTrustExpiry<T>>::get(&truster, &trustee) != Some(block) {
+                        continue;
+                    }
+                    // An expiry is only stored for a trusted pair, but never panic in on_idle.
+                    // Drop a stray expiry instead.
+                    let i = match <AccountTrustedAccountIndex<T>>::get(&truster, &trustee) {
+                        Some(i) => i,
+                        None => {
+                            <AccountTrustExpiry<T>>::remove(&truster, &trustee);
+                            continue;
+                        }
+                    };
+                    if Self::remove_trust(&truster, &trustee, i).is_ok() {
+                        Self::deposit_event(Event::TrustExpired(truster, trustee));
+                    }
+                }
+                if !finished {
+                    break;
+                }
+                block += One::one();
+            }
+
+            if block > now && <TrustExpiryQueue<T>>::iter_keys().next().is_none() {
+                <NextTrustExpiryBlock<T>>::kill();
+            } else {
+                <NextTrustExpiryBlock<T>>::put(block);
+            }
+            used
+        }
+
+        // Clear the outgoing trust of reaped accounts in KilledAccountQueue until the queue is
+        // empty or the weight runs out. The accounts cannot have had any deposits held, so none
+        // are released. An account that has been created again since it was reaped keeps its
+        // trust, and can clear it itself.
+        pub(crate) fn clear_killed_accounts(remaining_weight: Weight) -> Weight {
+            let read = T::DbWeight::get().reads(1);
+            // Checking the account exists, reading its count and removing it from the queue.
+            let step = T::DbWeight::get().reads_writes(2, 2);
+            let per_pair = T::WeightInfo::expire_trust();
+            let mut used = Weight::zero();
+
+            loop {
+                if used.saturating_add(read).any_gt(remaining_weight) {
+                    break;
+                }
+                used = used.saturating_add(read);
+                let who = match <KilledAccountQueue<T>>::iter_keys().next() {
+                    Some(who) => who,
+                    None => break,
+                };
+                if used.saturating_add(step).any_gt(remaining_weight) {
+                    break;
+                }
+                used = used.saturating_add(step);
+                if <frame_system::Pallet<T>>::account_exists(&who) {
+                    <KilledAccountQueue<T>>::remove(&who);
+                    continue;
+                }
+
+                // Untrust from the end of the list so no accounts have to be moved.
+                let mut count = <AccountTrustedAccountListCount<T>>::get(&who);
+                let mut removed = 0;
+                while count > 0 {
+                    if used.saturating_add(per_pair).any_gt(remaining_weight) {
+                        break;
+                    }
+                    used = used.saturating_add(per_pair);
+                    let account = match <AccountTrustedAccountList<T>>::get(&who, count - 1) {
+                        Some(account) => account,
+                        None => {
+                            count = 0;
+                            break;
+                        }
+                    };
+                    Self::remove_trust_pair(&who, &account, count);
+                    Self::deposit_event(Event::AccountUntrusted(who.clone(), account));
+                    count -= 1;
+                    removed += 1;
+                }
+                if removed > 0 {
+                    Self::deposit_event(Event::TrustedAccountsCleared(
+                        who.clone(),
+                        removed,
+                        count > 0,
+                    ));
+                }
+                if count > 0 {
+                    break;
+                }
+                <AccountTrustedAccountListCount<T>>::remove(&who);
+                <KilledAccountQueue<T>>::remove(&who);
+            }
+            used
+        }
+
+        // Check that the trust lists, their counts and their indexes are consistent with each
+        // other, and that the reverse lists mirror the forward lists exactly.
+        #[cfg(any(feature = "try-runtime", test))]
+        pub(crate) fn do_try_state() -> Result<(), TryRuntimeError> {
+            use frame_support::{sp_runtime::Saturating, traits::fungible::InspectHold};
+            use sp_std::collections::btree_map::BTreeMap;
+
+            // The trust lists are only partly migrated until the migration has finished.
+            if <MigrationInProgress<T>>::exists() {
+                return Ok(());
+            }
+
+            let mut pairs = 0u32;
+            for (truster, count) in <AccountTrustedAccountListCount<T>>::iter() {
+                ensure!(
+                    count <= T::MaxTrustedAccounts::get(),
+                    "Trusted account list longer than MaxTrustedAccounts"
+                );
+                for i in 0..count {
+                    let trustee = <AccountTrustedAccountList<T>>::get(&truster, i)
+                        .ok_or("Trusted account list shorter than its count")?;
+                    ensure!(truster != trustee, "Account trusts itself");
+                    ensure!(
+                        <AccountTrustedAccountIndex<T>>::get(&truster, &trustee) == Some(i + 1),
+                        "Trusted account index does not point at its list slot"
+                    );
+                    ensure!(
+                        <AccountTrustingAccountIndex<T>>::contains_key(&trustee, &truster),
+                        "Trust pair missing from the reverse index"
+                    );
+                }
+                pairs += count;
+            }
+            // Every index entry was matched to a distinct slot above, so equal totals rule out
+            // stray entries and duplicates.
+            ensure!(
+                <AccountTrustedAccountList<T>>::iter().count() as u32 == pairs,
+                "Trusted account list longer than its count"
+            );
+            ensure!(
+                <AccountTrustedAccountIndex<T>>::iter().count() as u32 == pairs,
+                "Trusted account index has entries not in the list"
+            );
+
+            let mut reverse_pairs = 0u32;
+            for (trustee, count) in <AccountTrustingAccountListCount<T>>::iter() {
+                for i in 0..count {
+                    let truster = <AccountTrustingAccountList<T>>::get(&trustee, i)
+                        .ok_or("Trusting account list shorter than its count")?;
+                    ensure!(
+                        <AccountTrustingAccountIndex<T>>::get(&trustee, &truster) == Some(i + 1),
+                        "Trusting account index does not point at its list slot"
+                    );
+                }
+                reverse_pairs += count;
+            }
+            ensure!(
+                <AccountTrustingAccountList<T>>::iter().count() as u32 == reverse_pairs,
+                "Trusting account list longer than its count"
+            );
+            ensure!(
+                <AccountTrustingAccountIndex<T>>::iter().count() as u32 == reverse_pairs,
+                "Trusting account index has entries not in the list"
+            );
+            ensure!(
+                reverse_pairs == pairs,
+                "Reverse index does not match trust pairs"
+            );
+
+            // Levels and expiries are only stored for existing trust pairs.
+            for (truster, trustee) in <AccountTrustLevel<T>>::iter_keys() {
+                ensure!(
+                    <AccountTrustedAccountIndex<T>>::contains_key(&truster, &trustee),
+                    "Trust level stored for a pair that is not trusted"
+                );
+            }
+            for (truster, trustee) in <AccountTrustExpiry<T>>::iter_keys() {
+                ensure!(
+                    <AccountTrustedAccountIndex<T>>::contains_key(&truster, &trustee),
+                    "Trust expiry stored for a pair that is not trusted"
+                );
+            }
+
+            // Deposits are only recorded for existing trust pairs, and the deposits recorded for
+            // each truster add up to the amount held from it.
+            let mut deposits = BTreeMap::<T::AccountId, BalanceOf<T>>::new();
+            for (truster, trustee, deposit) in <AccountTrustDeposit<T>>::iter() {
+                ensure!(
+                    <AccountTrustedAccountIndex<T>>::contains_key(&truster, &trustee),
+                    "Trust deposit recorded for a pair that is not trusted"
+                );
+                let total = deposits.entry(truster).or_default();
+                *total = total.saturating_add(deposit);
+            }
+            for truster in <AccountTrustedAccountListCount<T>>::iter_keys() {
+                ensure!(
+                    T::Currency::balance_on_hold(&HoldReason::TrustDeposit.into(), &truster)
+                        == deposits.get(&truster).copied().unwrap_or_default(),
+                    "Trust deposits held do not match the deposits recorded"
+                );
+            }
+
+            Ok(())
+        }
+
+        // Append a trust pair given the number of accounts the truster already trusts.
+        pub(crate) fn insert_trust(sender: &T::AccountId, account: &T::AccountId, count: u32) {
+            // Insert the new account at the end of the list.
+            <AccountTrustedAccountList<T>>::insert(sender, count, account);
+            // Update the size of the list.
+            <AccountTrustedAccountListCount<T>>::insert(sender, count + 1);
+            // Store index + 1 for this trust pair.
+            <AccountTrustedAccountIndex<T>>::insert(sender, account, count + 1);
+            // Get the total number of accounts that already trust the account.
+            let trusting_count = <AccountTrustingAccountListCount<T>>::get(account);
+            // Insert the sender at the end of the reverse list.
+            <AccountTrustingAccountList<T>>::insert(account, trusting_count, sender);
+            // Update the size of the reverse list.
+            <AccountTrustingAccountListCount<T>>::insert(account, trusting_count + 1);
+            // Store index + 1 for the reverse trust pair.
+            <AccountTrustingAccountIndex<T>>::insert(account, sender, trusting_count + 1);
+        }
+
+        // Remove a trust pair given the index + 1 of the trustee in the truster's list.
+        fn remove_trust(sender: &T::AccountId, account: &T::AccountId, i: u32) -> DispatchResult {
+            // Get the deposit held for this trust pair. Pairs created without one have none.
+            let deposit = <AccountTrustDeposit<T>>::get(sender, account);
+            if deposit.is_zero() {
+                Self::remove_trust_pair(sender, account, i);
+                return Ok(());
+            }
+            // Release the deposit for this trust pair before touching storage, so the pair is
+            // left intact if it fails. on_idle is not transactional.
+            let released = T::Currency::release(
+                &HoldReason::TrustDeposit.into(),
+                sender,
+                deposit,
+                Precision::BestEffort,
+            )?;
+            Self::remove_trust_pair(sender, account, i);
+            // Emit the event.
+            Self::deposit_event(Event::TrustDepositReleased(sender.clone(), released));
+            Ok(())
+        }
+
+        // Remove a trust pair from storage without releasing the deposit.
+        fn remove_trust_pair(sender: &T::AccountId, account: &T::AccountId, i: u32) {
             // Delete the index from state.
-            <AccountTrustedAccountIndex<T>>::remove(&sender, &account);
+            <AccountTrustedAccountIndex<T>>::remove(sender, account);
             // Get the list length.
-            let count = <AccountTrustedAccountListCount<T>>::get(&sender);
+            let count = <AccountTrustedAccountListCount<T>>::get(sender);
             // Check if this is not the last account.
             if i != count {
                 // Get the last account.
                 let moving_account =
-                    <AccountTrustedAccountList<T>>::get(&sender, count - 1).unwrap();
+                    <AccountTrustedAccountList<T>>::get(sender, count - 1).unwrap();
                 // Overwrite the account being untrusted with the last account.
-                <AccountTrustedAccountList<T>>::insert(&sender, i - 1, &moving_account);
+                <AccountTrustedAccountList<T>>::insert(sender, i - 1, &moving_account);
                 // Update the index + 1 of the last account.
-                <AccountTrustedAccountIndex<T>>::insert(&sender, moving_account, i);
+                <AccountTrustedAccountIndex<T>>::insert(sender, moving_account, i);
             }
             // Remove the last account.
-            <AccountTrustedAccountList<T>>::remove(&sender, count - 1);
-            <AccountTrustedAccountListCount<T>>::insert(&sender, count - 1);
-            // Emit the event.
-            Self::deposit_event(Event::AccountUntrusted(sender, account));
-            // Return a successful DispatchResultWithPostInfo
-            Ok(())
+            <AccountTrustedAccountList<T>>::remove(sender, count - 1);
+            <AccountTrustedAccountListCount<T>>::insert(sender, count - 1);
+            // Get the index + 1 of the sender in the reverse list.
+            let i = <AccountTrustingAccountIndex<T>>::take(account, sender).unwrap();
+            // Get the reverse list length.
+            let count = <AccountTrustingAccountListCount<T>>::get(account);
+            // Check if this is not the last account.
+            if i != count {
+                // Get the last account.
+                let moving_account =
+                    <AccountTrustingAccountList<T>>::get(account, count - 1).unwrap();
+                // Overwrite the sender with the last account.
+                <AccountTrustingAccountList<T>>::insert(account, i - 1, &moving_account);
+                // Update the index + 1 of the last account.
+                <AccountTrustingAccountIndex<T>>::insert(account, moving_account, i);
+            }
+            // Remove the last account.
+            <AccountTrustingAccountList<T>>::remove(account, count - 1);
+            <AccountTrustingAccountListCount<T>>::insert(account, count - 1);
+            // Delete the trust level, expiry and deposit record.
+            <AccountTrustLevel<T>>::remove(sender, account);
+            <AccountTrustExpiry<T>>::remove(sender, account);
+            <AccountTrustDeposit<T>>::remove(sender, account);
+        }
+    }
+
+    // Queue an account that has been reaped so on_idle clears its outgoing trust. This hook is
+    // not charged for, so it only does a constant amount of work. While a migration is running
+    // the trust lists cannot be read, so the account is queued without checking them.
+    impl<T: Config> OnKilledAccount<T::AccountId> for Pallet<T> {
+        fn on_killed_account(who: &T::AccountId) {
+            if <MigrationInProgress<T>>::exists()
+                || <AccountTrustedAccountListCount<T>>::get(who) > 0
+            {
+                <KilledAccountQueue<T>>::insert(who, ());
+            }
         }
     }
 
     impl<T: Config> Pallet<T> {
         pub fn is_trusted(account: T::AccountId, trustee: T::AccountId) -> bool {
             AccountTrustedAccountIndex::<T>::contains_key(&account, &trustee)
+                && !Self::is_expired(
+                    &account,
+                    &trustee,
+                    <frame_system::Pallet<T>>::block_number(),
+                )
         }
 
         pub fn is_trusted_only_deep(account: T::AccountId, trustee: T::AccountId) -> bool {
             let count = AccountTrustedAccountListCount::<T>::get(&account);
             let mut i = 0;
             while i < count {
-                if AccountTrustedAccountIndex::<T>::contains_key(
-                    AccountTrustedAccountList::<T>::get(&account, i).unwrap(),
-                    &trustee,
-                ) {
+                let account_trusted = AccountTrustedAccountList::<T>::get(&account, i).unwrap();
+                if Self::is_trusted(account.clone(), account_trusted.clone())
+                    && Self::is_trusted(account_trusted, trustee.clone())
+                {
                     return true;
                 }
 
@@ -168,26 +991,113 @@ pub mod pallet {
         }
 
         pub fn is_trusted_deep(account: T::AccountId, trustee: T::AccountId) -> bool {
-            if AccountTrustedAccountIndex::<T>::contains_key(&account, &trustee) {
+            if Self::is_trusted(account.clone(), trustee.clone()) {
                 return true;
             }
 
             Self::is_trusted_only_deep(account, trustee)
         }
 
+        /// The weight of `is_trusted_deep` in the worst case, where every trust pair of the
+        /// truster is read.
+        pub fn is_trusted_deep_weight() -> Weight {
+            T::WeightInfo::is_trusted_deep(T::MaxTrustedAccounts::get())
+        }
+
+        pub fn trust_level(account: T::AccountId, trustee: T::AccountId) -> Option<T::TrustLevel> {
+            if !Self::is_trusted(account.clone(), trustee.clone()) {
+                return None;
+            }
+
+            Some(AccountTrustLevel::<T>::get(&account, &trustee))
+        }
+
+        pub fn is_trusted_with_level(
+            account: T::AccountId,
+            trustee: T::AccountId,
+            min_level: T::TrustLevel,
+        ) -> bool {
+            match Self::trust_level(account, trustee) {
+                Some(level) => level >= min_level,
+                None => false,
+            }
+        }
+
+        pub fn is_trusted_only_deep_with_level(
+            account: T::AccountId,
+            trustee: T::AccountId,
+            min_level: T::TrustLevel,
+        ) -> bool {
+            let count = AccountTrustedAccountListCount::<T>::get(&account);
+            let mut i = 0;
+            while i < count {
+                let account_trusted = AccountTrustedAccountList::<T>::get(&account, i).unwrap();
+                if Self::is_trusted_with_level(account.clone(), account_trusted.clone(), min_level)
+                    && Self::is_trusted_with_level(account_trusted, trustee.clone(), min_level)
+                {
+                    return true;
+                }
+
+                i += 1;
+            }
+
+            false
+        }
+
+        pub fn is_trusted_deep_with_level(
+            account: T::AccountId,
+            trustee: T::AccountId,
+            min_level: T::TrustLevel,
+        ) -> bool {
+            if Self::is_trusted_with_level(account.clone(), trustee.clone(), min_level) {
+                return true;
+            }
+
+            Self::is_trusted_only_deep_with_level(account, trustee, min_level)
+        }
+
         pub fn trusted_by(account: T::AccountId) -> sp_std::prelude::Vec<T::AccountId> {
             let mut accounts = sp_std::prelude::Vec::new();
             let count = AccountTrustedAccountListCount::<T>::get(&account);
+            let now = <frame_system::Pallet<T>>::block_number();
 
             let mut i = 0;
             while i < count {
-                accounts.push(AccountTrustedAccountList::<T>::get(&account, i).unwrap());
+                let account_trusted = AccountTrustedAccountList::<T>::get(&account, i).unwrap();
+                if !Self::is_expired(&account, &account_trusted, now) {
+                    accounts.push(account_trusted);
+                }
                 i += 1;
             }
 
             accounts
         }
 
+        // Page through the accounts trusted by an account by list index. Untrusting swaps the
+        // last entry of the list into the removed slot, so if the list changes between calls
+        // an account can be skipped or returned twice.
+        pub fn trusted_by_paged(
+            account: T::AccountId,
+            start: u32,
+            limit: u32,
+        ) -> (sp_std::prelude::Vec<T::AccountId>, u32) {
+            let mut accounts = sp_std::prelude::Vec::new();
+            let count = AccountTrustedAccountListCount::<T>::get(&account);
+            let end = start.saturating_add(limit).min(count);
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            let mut i = start;
+            while i < end {
+                let account_trusted = AccountTrustedAccountList::<T>::get(&account, i).unwrap();
+                if !Self::is_expired(&account, &account_trusted, now) {
+                    accounts.push(account_trusted);
+                }
+                i += 1;
+            }
+
+            (accounts, count)
+        }
+
         pub fn trusted_by_that_trust(
             account: T::AccountId,
             account_is_trusted_by_trusted: T::AccountId,
@@ -206,5 +1116,117 @@ pub mod pallet {
 
             accounts_trusted_that_trust
         }
+
+        // A page of the trust graph in storage order: each truster after `start` with the
+        // accounts it trusts. Accounts that trust no others are skipped.
+        pub fn trust_graph(
+            start: Option<T::AccountId>,
+            limit: u32,
+        ) -> sp_std::prelude::Vec<(T::AccountId, sp_std::prelude::Vec<T::AccountId>)> {
+            let trusters = match start {
+                Some(start) => AccountTrustedAccountListCount::<T>::iter_from(
+                    AccountTrustedAccountListCount::<T>::hashed_key_for(start),
+                ),
+                None => AccountTrustedAccountListCount::<T>::iter(),
+            };
+
+            trusters
+                .filter_map(|(truster, _)| {
+                    let trustees = Self::trusted_by(truster.clone());
+                    if trustees.is_empty() {
+                        None
+                    } else {
+                        Some((truster, trustees))
+                    }
+                })
+                .take(limit as usize)
+                .collect()
+        }
+
+        // The expiry of each trust pair of the account that has one, including pairs that have
+        // expired but not been pruned yet.
+        pub fn trust_expiries(
+            account: T::AccountId,
+        ) -> sp_std::prelude::Vec<(T::AccountId, BlockNumberFor<T>)> {
+            AccountTrustExpiry::<T>::iter_prefix(&account).collect()
+        }
+
+        pub fn trusting(account: T::AccountId) -> sp_std::prelude::Vec<T::AccountId> {
+            let mut accounts = sp_std::prelude::Vec::new();
+            let count = AccountTrustingAccountListCount::<T>::get(&account);
+
+            let mut i = 0;
+            while i < count {
+                accounts.push(AccountTrustingAccountList::<T>::get(&account, i).unwrap());
+                i += 1;
+            }
+
+            accounts
+        }
+
+        pub fn trusting_count(account: T::AccountId) -> u32 {
+            AccountTrustingAccountListCount::<T>::get(&account)
+        }
+
+        // The number of anchors that directly trust the account.
+        pub fn trust_score(account: &T::AccountId, anchors: &[T::AccountId]) -> u32 {
+            anchors
+                .iter()
+                .filter(|anchor| Self::is_trusted((*anchor).clone(), account.clone()))
+                .count() as u32
+        }
+
+        // Trust pairs changed in the current block, read from its events, that involve the
+        // account. Each entry is [truster, trustee, trusted]. Not for use on-chain.
+        pub fn trust_changes(
+            account: T::AccountId,
+        ) -> sp_std::prelude::Vec<(T::AccountId, T::AccountId, bool)>
+        where
+            <T as Config>::RuntimeEvent: TryInto<Event<T>>,
+        {
+            Self::all_trust_changes()
+                .into_iter()
+                .filter(|(truster, trustee, _)| *truster == account || *trustee == account)
+                .collect()
+        }
+
+        // All trust pairs changed in the current block, in the order they were changed, read
+        // from its events. Each entry is [truster, trustee, trusted]. Not for use on-chain.
+        pub fn all_trust_changes() -> sp_std::prelude::Vec<(T::AccountId, T::AccountId, bool)>
+        where
+            <T as Config>::RuntimeEvent: TryInto<Event<T>>,
+        {
+            let mut changes = sp_std::prelude::Vec::new();
+
+            for record in <frame_system::Pallet<T>>::read_events_no_consensus() {
+                let event = <T as Config>::RuntimeEvent::from(record.event);
+                let change = match event.try_into() {
+                    Ok(Event::AccountTrusted(truster, trustee, _)) => (truster, trustee, true),
+                    Ok(Event::AccountUntrusted(truster, trustee)) => (truster, trustee, false),
+                    Ok(Event::AccountForceTrusted(truster, trustee)) => (truster, trustee, true),
+                    Ok(Event::AccountForceUntrusted(truster, trustee)) => (truster, trustee, false),
+                    Ok(Event::TrustExpired(truster, trustee)) => (truster, trustee, false),
+                    _ => continue,
+                };
+                changes.push(change);
+            }
+
+            changes
+        }
+
+        pub fn trust_path(
+            from: T::AccountId,
+            to: T::AccountId,
+            max_depth: u32,
+            max_nodes: u32,
+        ) -> Option<sp_std::prelude::Vec<T::AccountId>> {
+            let path = crate::graph::trust_path(from, to, max_depth, max_nodes, |account| {
+                Ok::<_, sp_std::convert::Infallible>(Self::trusted_by(account.clone()))
+            });
+            match path {
+                Ok(path) => path,
+                Err(never) => match never {},
+            }
+        }
     }
 }
@@ -0,0 +1,284 @@
+//! Storage migrations for the trusted accounts pallet.
+
+use super::*;
+use frame_support::{
+    pallet_prelude::*,
+    storage::{unhashed, StoragePrefixedMap},
+    storage_alias,
+    traits::{fungible::MutateHold, tokens::Precision, OnRuntimeUpgrade, StorageVersion},
+    weights::WeightMeter,
+};
+#[cfg(feature = "try-runtime")]
+use sp_std::prelude::Vec;
+
+/// Migrate from storage version 0, where all maps were keyed by the `Identity` hasher, to
+/// storage version 1, where accounts are keyed by `Blake2_128Concat`.
+///
+/// The migration runs in `on_idle` over as many blocks as it needs. The pallet's calls fail with
+/// `MigrationInProgress` until it has finished, and queries made in the meantime only see the
+/// trusters migrated so far.
+pub mod v1 {
+    use super::*;
+
+    pub(crate) mod v0 {
+        use super::*;
+
+        #[storage_alias]
+        pub type AccountTrustedAccountListCount<T: Config> = StorageMap<
+            Pallet<T>,
+            Identity,
+            <T as frame_system::Config>::AccountId,
+            u32,
+            ValueQuery,
+        >;
+
+        #[storage_alias]
+        pub type AccountTrustedAccountList<T: Config> = StorageDoubleMap<
+            Pallet<T>,
+            Identity,
+            <T as frame_system::Config>::AccountId,
+            Twox64Concat,
+            u32,
+            <T as frame_system::Config>::AccountId,
+        >;
+
+        #[storage_alias]
+        pub type AccountTrustedAccountIndex<T: Config> = StorageDoubleMap<
+            Pallet<T>,
+            Identity,
+            <T as frame_system::Config>::AccountId,
+            Blake2_128Concat,
+            <T as frame_system::Config>::AccountId,
+            u32,
+        >;
+
+        #[storage_alias]
+        pub type AccountTrustingAccountListCount<T: Config> = StorageMap<
+            Pallet<T>,
+            Identity,
+            <T as frame_system::Config>::AccountId,
+            u32,
+            ValueQuery,
+        >;
+
+        #[storage_alias]
+        pub type AccountTrustingAccountList<T: Config> = StorageDoubleMap<
+            Pallet<T>,
+            Identity,
+            <T as frame_system::Config>::AccountId,
+            Twox64Concat,
+            u32,
+            <T as frame_system::Config>::AccountId,
+        >;
+
+        #[storage_alias]
+        pub type AccountTrustingAccountIndex<T: Config> = StorageDoubleMap<
+            Pallet<T>,
+            Identity,
+            <T as frame_system::Config>::AccountId,
+            Blake2_128Concat,
+            <T as frame_system::Config>::AccountId,
+            u32,
+        >;
+    }
+
+    // The trusters whose lists have not been migrated yet, with the length of each list.
+    #[storage_alias]
+    pub(crate) type MigrationQueue<T: Config> =
+        StorageMap<Pallet<T>, Blake2_128Concat, <T as frame_system::Config>::AccountId, u32>;
+
+    // The most keys removed from each old reverse index map in a block.
+    const CLEAR_LIMIT: u32 = 128;
+
+    /// The stages of the migration, run in order.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+    pub enum MigrationStage<AccountId> {
+        /// Moving the trusters and the lengths of their lists into the migration queue.
+        QueueTrusters,
+        /// Removing the old reverse index, which is rebuilt from the lists.
+        ClearReverseIndex,
+        /// Migrating the list of each queued truster. [truster, list length, next list index]
+        MigrateLists(Option<(AccountId, u32, u32)>),
+    }
+
+    // The stage to continue with, or `None` once the migration has finished. `Err` holds the
+    // stage to resume in a later block when the weight has run out.
+    type StepResult<T> = Result<
+        Option<MigrationStage<<T as frame_system::Config>::AccountId>>,
+        MigrationStage<<T as frame_system::Config>::AccountId>,
+    >;
+
+    /// Re-key the trusted account maps and rebuild the reverse index from them, as chains
+    /// running version 0 may not have populated it.
+    ///
+    /// Self trust, duplicate pairs and pairs beyond `MaxTrustedAccounts` in a list are dropped.
+    /// Version 0 did not record deposits for each pair, so any trust deposit held from a truster
+    /// is released and the migrated pairs hold none, like pairs created at genesis.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() != 0 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            // Start the migration, which continues in on_idle.
+            MigrationInProgress::<T>::put(MigrationStage::QueueTrusters);
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(1, 2)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, frame_support::sp_runtime::TryRuntimeError> {
+            ensure!(
+                !MigrationInProgress::<T>::exists(),
+                "a migration is already in progress"
+            );
+            Ok(Vec::new())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+            ensure!(
+                StorageVersion::get::<Pallet<T>>() == 1,
+                "storage version not updated"
+            );
+            Ok(())
+        }
+    }
+
+    /// Continue the migration until it finishes or the weight runs out. Returns the weight used.
+    pub(crate) fn step<T: Config>(remaining_weight: Weight) -> Weight {
+        let mut meter = WeightMeter::with_limit(remaining_weight);
+        // Read the stage and store it again at the end.
+        if meter
+            .try_consume(T::DbWeight::get().reads_writes(1, 1))
+            .is_err()
+        {
+            return Weight::zero();
+        }
+        let mut stage = match MigrationInProgress::<T>::get() {
+            Some(stage) => stage,
+            None => return meter.consumed(),
+        };
+
+        loop {
+            let result = match stage {
+                MigrationStage::QueueTrusters => queue_trusters::<T>(&mut meter),
+                MigrationStage::ClearReverseIndex => clear_reverse_index::<T>(&mut meter),
+                MigrationStage::MigrateLists(current) => migrate_lists::<T>(&mut meter, current),
+            };
+            match result {
+                Ok(Some(next)) => stage = next,
+                Ok(None) => {
+                    MigrationInProgress::<T>::kill();
+                    return meter.consumed();
+                }
+                Err(paused) => {
+                    MigrationInProgress::<T>::put(paused);
+                    return meter.consumed();
+                }
+            }
+        }
+    }
+
+    // Move the trusters and the lengths of their lists into the queue. The old count map shares
+    // its prefix with the new one, so it is drained before anything is written under it.
+    fn queue_trusters<T: Config>(meter: &mut WeightMeter) -> StepResult<T> {
+        let mut counts = v0::AccountTrustedAccountListCount::<T>::drain();
+        loop {
+            if meter
+                .try_consume(T::DbWeight::get().reads_writes(1, 2))
+                .is_err()
+            {
+                return Err(MigrationStage::QueueTrusters);
+            }
+            match counts.next() {
+                Some((truster, count)) => MigrationQueue::<T>::insert(truster, count),
+                None => return Ok(Some(MigrationStage::ClearReverseIndex)),
+            }
+        }
+    }
+
+    // Remove the old reverse index, which may not have been populated. Nothing is written under
+    // its prefixes until this has finished.
+    fn clear_reverse_index<T: Config>(meter: &mut WeightMeter) -> StepResult<T> {
+        for prefix in [
+            v0::AccountTrustingAccountListCount::<T>::final_prefix(),
+            v0::AccountTrustingAccountList::<T>::final_prefix(),
+            v0::AccountTrustingAccountIndex::<T>::final_prefix(),
+        ] {
+            // Remove as many keys as the weight allows.
+            let per_key = T::DbWeight::get().reads_writes(1, 1);
+            let limit = meter
+                .remaining()
+                .checked_div_per_component(&per_key)
+                .map_or(CLEAR_LIMIT, |limit| limit.min(CLEAR_LIMIT.into()) as u32);
+            if limit == 0 {
+                return Err(MigrationStage::ClearReverseIndex);
+            }
+            // Keys removed earlier in the block would be counted against the limit again, so
+            // only one removal is made under a prefix in each block.
+            let result = unhashed::clear_prefix(&prefix, Some(limit), None);
+            meter.consume(per_key.saturating_mul(result.loops.max(1).into()));
+            if result.maybe_cursor.is_some() {
+                return Err(MigrationStage::ClearReverseIndex);
+            }
+        }
+        Ok(Some(MigrationStage::MigrateLists(None)))
+    }
+
+    // Migrate the list of each queued truster, rebuilding the reverse index as it goes. The old
+    // keys all differ from the new ones, so they can be taken one at a time.
+    fn migrate_lists<T: Config>(
+        meter: &mut WeightMeter,
+        mut current: Option<(T::AccountId, u32, u32)>,
+    ) -> StepResult<T> {
+        loop {
+            let (truster, count, mut i) = match current.take() {
+                Some(current) => current,
+                None => {
+                    // Take the next truster and release any trust deposit held from it.
+                    if meter
+                        .try_consume(T::DbWeight::get().reads_writes(3, 3))
+                        .is_err()
+                    {
+                        return Err(MigrationStage::MigrateLists(None));
+                    }
+                    let (truster, count) = match MigrationQueue::<T>::drain().next() {
+                        Some(queued) => queued,
+                        None => return Ok(None),
+                    };
+                    let _ = T::Currency::release_all(
+                        &HoldReason::TrustDeposit.into(),
+                        &truster,
+                        Precision::BestEffort,
+                    );
+                    (truster, count, 0)
+                }
+            };
+
+            while i < count {
+                if meter
+                    .try_consume(T::DbWeight::get().reads_writes(4, 8))
+                    .is_err()
+                {
+                    return Err(MigrationStage::MigrateLists(Some((truster, count, i))));
+                }
+                if let Some(trustee) = v0::AccountTrustedAccountList::<T>::take(&truster, i) {
+                    v0::AccountTrustedAccountIndex::<T>::remove(&truster, &trustee);
+                    // Drop pairs that would break the version 1 invariants.
+                    let trusted = AccountTrustedAccountListCount::<T>::get(&truster);
+                    if trusted < T::MaxTrustedAccounts::get()
+                        && truster != trustee
+                        && !AccountTrustedAccountIndex::<T>::contains_key(&truster, &trustee)
+                    {
+                        Pallet::<T>::insert_trust(&truster, &trustee, trusted);
+                    }
+                }
+                i += 1;
+            }
+        }
+    }
+}
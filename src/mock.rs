@@ -1,23 +1,23 @@
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use frame_support::{
+    traits::{ConstU16, ConstU32, ConstU64, Contains},
+    weights::constants::RocksDbWeight,
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
     traits::{BlakeTwo256, IdentityLookup},
+    BuildStorage,
 };
 
-type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
-    pub enum Test where
-        Block = Block,
-        NodeBlock = Block,
-        UncheckedExtrinsic = UncheckedExtrinsic,
+    pub enum Test
     {
         System: frame_system,
+        Balances: pallet_balances,
         TemplateModule: pallet_template,
     }
 );
@@ -26,37 +26,117 @@ impl system::Config for Test {
     type BaseCallFilter = frame_support::traits::Everything;
     type BlockWeights = ();
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type RuntimeOrigin = RuntimeOrigin;
     type RuntimeCall = RuntimeCall;
-    type Index = u64;
-    type BlockNumber = u64;
+    type Nonce = u64;
     type Hash = H256;
     type Hashing = BlakeTwo256;
     type AccountId = u64;
     type Lookup = IdentityLookup<Self::AccountId>;
-    type Header = Header;
+    type Block = Block;
     type RuntimeEvent = RuntimeEvent;
     type BlockHashCount = ConstU64<250>;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<u64>;
     type OnNewAccount = ();
-    type OnKilledAccount = ();
+    type OnKilledAccount = TemplateModule;
     type SystemWeightInfo = ();
     type SS58Prefix = ConstU16<42>;
     type OnSetCode = ();
-    type MaxConsumers = frame_support::traits::ConstU32<16>;
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type RuntimeFreezeReason = RuntimeFreezeReason;
+    type MaxHolds = ConstU32<1>;
 }
 
 impl pallet_template::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type MaxTrustedAccounts = ConstU32<4>;
+    type Currency = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type TrustDeposit = TrustDeposit;
+    type TrustLevel = u8;
+    type MaxBatchSize = ConstU32<3>;
+    type ForceOrigin = frame_system::EnsureRoot<u64>;
+    type WeightInfo = ();
+}
+
+frame_support::parameter_types! {
+    pub storage TrustDeposit: u64 = 10;
+    pub storage TrustRoots: Vec<u64> = vec![1];
+    pub TrustAnchors: Vec<u64> = vec![1, 2, 3, 4];
+}
+
+// Only allow transactions from accounts trusted by the first two trust roots.
+pub type CheckTrusted =
+    pallet_template::CheckTrustedSender<Test, TrustRoots, ConstU32<2>, TrustCalls>;
+
+// Raise the priority of transactions for each of the first three trust anchors that trusts the
+// sender.
+pub type PrioritizeTrusted =
+    pallet_template::PrioritizeTrustedSender<Test, TrustAnchors, ConstU32<3>, ConstU64<100>>;
+
+// Allow any account to trust and untrust.
+pub struct TrustCalls;
+impl Contains<RuntimeCall> for TrustCalls {
+    fn contains(call: &RuntimeCall) -> bool {
+        matches!(
+            call,
+            RuntimeCall::TemplateModule(
+                pallet_template::Call::trust_account { .. }
+                    | pallet_template::Call::untrust_account { .. }
+            )
+        )
+    }
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    system::GenesisConfig::default()
-        .build_storage::<Test>()
-        .unwrap()
-        .into()
+    let mut t = system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: (1..=9).map(|account| (account, 100)).collect(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+// Build genesis storage according to the mock runtime, run the test and then check that the
+// pallet's storage is still consistent.
+pub fn build_and_execute(test: impl FnOnce()) {
+    new_test_ext().execute_with(|| {
+        test();
+        TemplateModule::do_try_state().unwrap();
+    });
+}
+
+// Build genesis storage with the given trust pairs.
+pub fn new_test_ext_with_trusts(trusts: Vec<(u64, u64)>) -> sp_io::TestExternalities {
+    let mut t = system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    pallet_template::GenesisConfig::<Test> { trusts }
+        .assimilate_storage(&mut t)
+        .unwrap();
+    t.into()
 }
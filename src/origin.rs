@@ -0,0 +1,89 @@
+//! Origins that require the signer to be trusted by a given account.
+
+use crate::{Config, Pallet};
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use sp_std::marker::PhantomData;
+
+/// Ensure the origin is signed by an account that `Truster` trusts directly. The signer is
+/// returned on success.
+pub struct EnsureTrustedBy<T, Truster>(PhantomData<(T, Truster)>);
+
+impl<T: Config, Truster: Get<T::AccountId>> EnsureOrigin<T::RuntimeOrigin>
+    for EnsureTrustedBy<T, Truster>
+{
+    type Success = T::AccountId;
+
+    fn try_origin(o: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+        o.into().and_then(|o| match o {
+            RawOrigin::Signed(who) if Pallet::<T>::is_trusted(Truster::get(), who.clone()) => {
+                Ok(who)
+            }
+            r => Err(T::RuntimeOrigin::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+        let who: T::AccountId = frame_benchmarking::account("trusted", 0, 0);
+        ensure_trust_pair::<T>(&Truster::get(), &who)?;
+        Ok(RawOrigin::Signed(who).into())
+    }
+}
+
+/// Ensure the origin is signed by an account that `Truster` trusts directly or through an
+/// account it trusts. The signer is returned on success.
+///
+/// Checking the origin reads every trust pair of `Truster`, up to `MaxTrustedAccounts` of them,
+/// and this is not charged for by the origin check itself. Calls that use this origin must add
+/// [`Pallet::is_trusted_deep_weight`] to their weight.
+pub struct EnsureTrustedDeepBy<T, Truster>(PhantomData<(T, Truster)>);
+
+impl<T: Config, Truster: Get<T::AccountId>> EnsureOrigin<T::RuntimeOrigin>
+    for EnsureTrustedDeepBy<T, Truster>
+{
+    type Success = T::AccountId;
+
+    fn try_origin(o: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+        o.into().and_then(|o| match o {
+            RawOrigin::Signed(who) if Pallet::<T>::is_trusted_deep(Truster::get(), who.clone()) => {
+                Ok(who)
+            }
+            r => Err(T::RuntimeOrigin::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+        // Use the worst case of trust through an intermediary that is last in a full list, so
+        // every trust pair of the truster is read.
+        let truster = Truster::get();
+        let intermediary: T::AccountId = frame_benchmarking::account("intermediary", 0, 0);
+        let who: T::AccountId = frame_benchmarking::account("trusted", 0, 0);
+        let mut i = 0;
+        while crate::AccountTrustedAccountListCount::<T>::get(&truster)
+            < T::MaxTrustedAccounts::get() - 1
+        {
+            let filler: T::AccountId = frame_benchmarking::account("filler", i, 0);
+            ensure_trust_pair::<T>(&truster, &filler)?;
+            i += 1;
+        }
+        ensure_trust_pair::<T>(&truster, &intermediary)?;
+        ensure_trust_pair::<T>(&intermediary, &who)?;
+        Ok(RawOrigin::Signed(who).into())
+    }
+}
+
+// Make the truster trust the trustee, without holding a deposit, if it does not already.
+#[cfg(feature = "runtime-benchmarks")]
+fn ensure_trust_pair<T: Config>(truster: &T::AccountId, trustee: &T::AccountId) -> Result<(), ()> {
+    if truster == trustee || Pallet::<T>::is_trusted(truster.clone(), trustee.clone()) {
+        return Ok(());
+    }
+    let count = crate::AccountTrustedAccountListCount::<T>::get(truster);
+    if count >= T::MaxTrustedAccounts::get() {
+        return Err(());
+    }
+    Pallet::<T>::insert_trust(truster, trustee, count);
+    Ok(())
+}
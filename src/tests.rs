@@ -1,16 +1,28 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    migrations, mock::*, AccountTrustedAccountIndex, AccountTrustedAccountListCount,
+    AccountTrustingAccountListCount, EnsureTrustedBy, EnsureTrustedDeepBy, Error, Event,
+    HoldReason, TrustProvider,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{
+        fungible::{Inspect, InspectHold, MutateHold},
+        EnsureOrigin, Hooks, OnRuntimeUpgrade, StorageVersion,
+    },
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_runtime::{traits::SignedExtension, transaction_validity::InvalidTransaction, TokenError};
 
 #[test]
 fn trust_account_cant_trust_self_control() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
     });
 }
 
 #[test]
 fn trust_account_cant_trust_self() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         assert_noop!(
             TemplateModule::trust_account(RuntimeOrigin::signed(1), 1),
             Error::<Test>::TrustSelf
@@ -20,7 +32,7 @@ fn trust_account_cant_trust_self() {
 
 #[test]
 fn trust_account_already_trusted_control() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
         assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 3));
     });
@@ -28,7 +40,7 @@ fn trust_account_already_trusted_control() {
 
 #[test]
 fn trust_account_already_trusted() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
         assert_noop!(
             TemplateModule::trust_account(RuntimeOrigin::signed(1), 2),
@@ -37,9 +49,122 @@ fn trust_account_already_trusted() {
     });
 }
 
+#[test]
+fn trust_account_too_many_trusted_accounts_control() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 3));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 4));
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 4));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 5));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 6));
+    });
+}
+
+#[test]
+fn trust_account_too_many_trusted_accounts() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 3));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 4));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 5));
+        assert_noop!(
+            TemplateModule::trust_account(RuntimeOrigin::signed(1), 6),
+            Error::<Test>::TooManyTrustedAccounts
+        );
+    });
+}
+
+#[test]
+fn trust_account_insufficient_balance_control() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(9), 2));
+    });
+}
+
+#[test]
+fn trust_account_insufficient_balance() {
+    build_and_execute(|| {
+        assert_noop!(
+            TemplateModule::trust_account(RuntimeOrigin::signed(10), 2),
+            TokenError::FundsUnavailable
+        );
+    });
+}
+
+#[test]
+fn trust_account_deposit() {
+    build_and_execute(|| {
+        let reason = HoldReason::TrustDeposit.into();
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 10);
+        assert_eq!(Balances::balance(&1), 90);
+        System::assert_has_event(Event::TrustDepositHeld(1, 10).into());
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 3));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 20);
+        assert_eq!(Balances::balance(&1), 80);
+
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 2));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 10);
+        assert_eq!(Balances::balance(&1), 90);
+        System::assert_has_event(Event::TrustDepositReleased(1, 10).into());
+
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 3));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 0);
+        assert_eq!(Balances::balance(&1), 100);
+    });
+}
+
+#[test]
+fn trust_account_deposit_changed() {
+    build_and_execute(|| {
+        let reason = HoldReason::TrustDeposit.into();
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        TrustDeposit::set(&20);
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 3));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 30);
+
+        // The deposit held when the pair was created is released, not the current deposit.
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 2));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 20);
+        System::assert_has_event(Event::TrustDepositReleased(1, 10).into());
+
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 3));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 0);
+        assert_eq!(Balances::balance(&1), 100);
+    });
+}
+
+#[test]
+fn untrust_account_without_deposit() {
+    build_and_execute(|| {
+        let reason = HoldReason::TrustDeposit.into();
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::force_trust(RuntimeOrigin::root(), 1, 3));
+        assert_eq!(TemplateModule::account_trust_deposit(1, 2), 10);
+        assert_eq!(TemplateModule::account_trust_deposit(1, 3), 0);
+
+        // Removing the pair created without a deposit leaves the other pair's deposit held.
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 3));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 10);
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::TemplateModule(Event::TrustDepositReleased(..))
+        )));
+
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 2));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 0);
+        assert_eq!(TemplateModule::account_trust_deposit(1, 2), 0);
+    });
+}
+
 #[test]
 fn trust_account() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         assert_eq!(TemplateModule::account_trusted_account_list_count(1), 0);
 
         assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
@@ -78,7 +203,7 @@ fn trust_account() {
 
 #[test]
 fn untrust_account_not_trusted_control() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
         assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 2));
     });
@@ -86,7 +211,7 @@ fn untrust_account_not_trusted_control() {
 
 #[test]
 fn untrust_account_not_trusted() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         assert_noop!(
             TemplateModule::untrust_account(RuntimeOrigin::signed(1), 2),
             Error::<Test>::NotTrusted
@@ -96,7 +221,7 @@ fn untrust_account_not_trusted() {
 
 #[test]
 fn untrust_account() {
-    new_test_ext().execute_with(|| {
+    build_and_execute(|| {
         assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
         assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 3));
         assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 4));
@@ -120,3 +245,1228 @@ fn untrust_account() {
         assert_eq!(TemplateModule::account_trusted_account_index(1, 4), None);
     });
 }
+
+#[test]
+fn trust_account_trusting() {
+    build_and_execute(|| {
+        assert_eq!(TemplateModule::account_trusting_account_list_count(5), 0);
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 5));
+        assert_eq!(TemplateModule::account_trusting_account_list_count(5), 1);
+        let i = TemplateModule::account_trusting_account_index(5, 1).unwrap() - 1;
+        assert_eq!(
+            TemplateModule::account_trusting_account_list(5, i).unwrap(),
+            1
+        );
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(2), 5));
+        assert_eq!(TemplateModule::account_trusting_account_list_count(5), 2);
+        let i = TemplateModule::account_trusting_account_index(5, 2).unwrap() - 1;
+        assert_eq!(
+            TemplateModule::account_trusting_account_list(5, i).unwrap(),
+            2
+        );
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(3), 5));
+        assert_eq!(TemplateModule::trusting(5), vec![1, 2, 3]);
+        assert_eq!(TemplateModule::trusting_count(5), 3);
+    });
+}
+
+#[test]
+fn untrust_account_trusting() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 5));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(2), 5));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(3), 5));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(4), 5));
+        assert_eq!(TemplateModule::account_trusting_account_list_count(5), 4);
+
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(2), 5));
+        assert_eq!(TemplateModule::account_trusting_account_list_count(5), 3);
+        assert_eq!(TemplateModule::account_trusting_account_index(5, 2), None);
+        assert_eq!(TemplateModule::trusting(5), vec![1, 4, 3]);
+
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(3), 5));
+        assert_eq!(TemplateModule::account_trusting_account_list_count(5), 2);
+        assert_eq!(TemplateModule::account_trusting_account_index(5, 3), None);
+        assert_eq!(TemplateModule::trusting(5), vec![1, 4]);
+
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 5));
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(4), 5));
+        assert_eq!(TemplateModule::trusting_count(5), 0);
+        assert!(TemplateModule::trusting(5).is_empty());
+    });
+}
+
+// Run on_idle with the given weight each block until the migration finishes, returning the
+// number of blocks it took.
+fn run_migration(weight: Weight) -> u32 {
+    let mut blocks = 0;
+    while crate::MigrationInProgress::<Test>::exists() {
+        TemplateModule::on_idle(System::block_number(), weight);
+        blocks += 1;
+        assert!(blocks < 100, "migration made no progress");
+    }
+    blocks
+}
+
+#[test]
+fn migrate_to_v1() {
+    build_and_execute(|| {
+        use migrations::v1::v0;
+
+        StorageVersion::new(0).put::<TemplateModule>();
+        // Account 1 trusts 2 and 3, account 2 trusts 3.
+        v0::AccountTrustedAccountListCount::<Test>::insert(1, 2);
+        v0::AccountTrustedAccountList::<Test>::insert(1, 0, 2);
+        v0::AccountTrustedAccountList::<Test>::insert(1, 1, 3);
+        v0::AccountTrustedAccountIndex::<Test>::insert(1, 2, 1);
+        v0::AccountTrustedAccountIndex::<Test>::insert(1, 3, 2);
+        v0::AccountTrustedAccountListCount::<Test>::insert(2, 1);
+        v0::AccountTrustedAccountList::<Test>::insert(2, 0, 3);
+        v0::AccountTrustedAccountIndex::<Test>::insert(2, 3, 1);
+        // A stale reverse index entry.
+        v0::AccountTrustingAccountListCount::<Test>::insert(4, 1);
+        v0::AccountTrustingAccountList::<Test>::insert(4, 0, 1);
+        v0::AccountTrustingAccountIndex::<Test>::insert(4, 1, 1);
+
+        migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get::<TemplateModule>(), 1);
+
+        // The pallet's calls are disabled until the migration finishes.
+        assert_noop!(
+            TemplateModule::trust_account(RuntimeOrigin::signed(5), 6),
+            Error::<Test>::MigrationInProgress
+        );
+        assert_noop!(
+            TemplateModule::untrust_account(RuntimeOrigin::signed(1), 2),
+            Error::<Test>::MigrationInProgress
+        );
+        assert_noop!(
+            TemplateModule::force_untrust(RuntimeOrigin::root(), 1, 2),
+            Error::<Test>::MigrationInProgress
+        );
+
+        run_migration(Weight::MAX);
+
+        assert!(!v0::AccountTrustedAccountList::<Test>::contains_key(1, 0));
+        assert!(!v0::AccountTrustedAccountIndex::<Test>::contains_key(1, 2));
+        assert!(!v0::AccountTrustingAccountList::<Test>::contains_key(4, 0));
+        assert_eq!(TemplateModule::trusted_by(1), vec![2, 3]);
+        assert_eq!(TemplateModule::trusted_by(2), vec![3]);
+        assert_eq!(TemplateModule::account_trusted_account_index(1, 3), Some(2));
+        assert_eq!(TemplateModule::trusting(2), vec![1]);
+        assert_eq!(TemplateModule::trusting(4), Vec::<u64>::new());
+        assert_eq!(TemplateModule::trusting(3).len(), 2);
+        assert!(TemplateModule::is_trusted_deep(1, 3));
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 2));
+
+        // Running the migration again does nothing.
+        migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        assert!(!crate::MigrationInProgress::<Test>::exists());
+    });
+}
+
+#[test]
+fn migrate_to_v1_over_many_blocks() {
+    build_and_execute(|| {
+        use migrations::v1::v0;
+
+        StorageVersion::new(0).put::<TemplateModule>();
+        for truster in 1..=4 {
+            v0::AccountTrustedAccountListCount::<Test>::insert(truster, 3);
+            for (i, trustee) in (5..=7).enumerate() {
+                v0::AccountTrustedAccountList::<Test>::insert(truster, i as u32, trustee);
+                v0::AccountTrustedAccountIndex::<Test>::insert(truster, trustee, i as u32 + 1);
+            }
+        }
+
+        migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        // Enough weight for a few trust pairs in each block.
+        let weight = RocksDbWeight::get().reads_writes(20, 40);
+        assert!(run_migration(weight) > 1);
+
+        for truster in 1..=4 {
+            assert_eq!(TemplateModule::trusted_by(truster), vec![5, 6, 7]);
+        }
+        assert_eq!(TemplateModule::trusting_count(6), 4);
+    });
+}
+
+#[test]
+fn migrate_to_v1_drops_invalid_trust() {
+    build_and_execute(|| {
+        use migrations::v1::v0;
+
+        StorageVersion::new(0).put::<TemplateModule>();
+        // Account 1 trusts itself, 2 twice and then more accounts than MaxTrustedAccounts.
+        let trustees = [1, 2, 2, 3, 4, 5, 6, 7];
+        v0::AccountTrustedAccountListCount::<Test>::insert(1, trustees.len() as u32);
+        for (i, trustee) in trustees.into_iter().enumerate() {
+            v0::AccountTrustedAccountList::<Test>::insert(1, i as u32, trustee);
+            v0::AccountTrustedAccountIndex::<Test>::insert(1, trustee, i as u32 + 1);
+        }
+        // Version 0 did not record deposits for each pair.
+        assert_ok!(Balances::hold(&HoldReason::TrustDeposit.into(), &1, 30));
+
+        migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        run_migration(Weight::MAX);
+
+        assert_eq!(TemplateModule::trusted_by(1), vec![2, 3, 4, 5]);
+        assert!(!v0::AccountTrustedAccountList::<Test>::contains_key(1, 7));
+        assert!(!v0::AccountTrustedAccountIndex::<Test>::contains_key(1, 7));
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::TrustDeposit.into(), &1),
+            0
+        );
+        assert_eq!(Balances::balance(&1), 100);
+        assert_eq!(TemplateModule::account_trust_deposit(1, 2), 0);
+    });
+}
+
+#[test]
+fn trust_accounts() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_accounts(
+            RuntimeOrigin::signed(1),
+            vec![2, 3, 4].try_into().unwrap()
+        ));
+        assert_eq!(TemplateModule::trusted_by(1), vec![2, 3, 4]);
+        System::assert_has_event(Event::AccountTrusted(1, 2, 0).into());
+        System::assert_has_event(Event::AccountTrusted(1, 3, 0).into());
+        System::assert_has_event(Event::AccountTrusted(1, 4, 0).into());
+    });
+}
+
+#[test]
+fn trust_accounts_is_atomic() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 3));
+        assert_noop!(
+            TemplateModule::trust_accounts(
+                RuntimeOrigin::signed(1),
+                vec![2, 3, 4].try_into().unwrap()
+            ),
+            Error::<Test>::AlreadyTrusted
+        );
+        assert_noop!(
+            TemplateModule::trust_accounts(
+                RuntimeOrigin::signed(1),
+                vec![2, 1].try_into().unwrap()
+            ),
+            Error::<Test>::TrustSelf
+        );
+    });
+}
+
+#[test]
+fn untrust_accounts() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_accounts(
+            RuntimeOrigin::signed(1),
+            vec![2, 3, 4].try_into().unwrap()
+        ));
+        assert_ok!(TemplateModule::untrust_accounts(
+            RuntimeOrigin::signed(1),
+            vec![2, 4].try_into().unwrap()
+        ));
+        assert_eq!(TemplateModule::trusted_by(1), vec![3]);
+        System::assert_has_event(Event::AccountUntrusted(1, 2).into());
+        System::assert_has_event(Event::AccountUntrusted(1, 4).into());
+    });
+}
+
+#[test]
+fn untrust_accounts_is_atomic() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_accounts(
+            RuntimeOrigin::signed(1),
+            vec![2, 3].try_into().unwrap()
+        ));
+        assert_noop!(
+            TemplateModule::untrust_accounts(
+                RuntimeOrigin::signed(1),
+                vec![2, 4].try_into().unwrap()
+            ),
+            Error::<Test>::NotTrusted
+        );
+    });
+}
+
+#[test]
+fn clear_trusted_accounts() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_accounts(
+            RuntimeOrigin::signed(1),
+            vec![2, 3, 4].try_into().unwrap()
+        ));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(5), 3));
+
+        assert_ok!(TemplateModule::clear_trusted_accounts(
+            RuntimeOrigin::signed(1),
+            2
+        ));
+        assert_eq!(TemplateModule::trusted_by(1), vec![2]);
+        assert_eq!(TemplateModule::trusting(3), vec![5]);
+        System::assert_last_event(Event::TrustedAccountsCleared(1, 2, true).into());
+
+        assert_ok!(TemplateModule::clear_trusted_accounts(
+            RuntimeOrigin::signed(1),
+            2
+        ));
+        assert!(TemplateModule::trusted_by(1).is_empty());
+        assert_eq!(TemplateModule::account_trusted_account_index(1, 2), None);
+        assert_eq!(TemplateModule::trusting_count(2), 0);
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::TrustDeposit.into(), &1),
+            0
+        );
+        System::assert_last_event(Event::TrustedAccountsCleared(1, 1, false).into());
+    });
+}
+
+#[test]
+fn clear_trusted_accounts_refunds_weight() {
+    build_and_execute(|| {
+        use crate::WeightInfo;
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        let info = TemplateModule::clear_trusted_accounts(RuntimeOrigin::signed(1), 100).unwrap();
+        assert_eq!(
+            info.actual_weight,
+            Some(<() as WeightInfo>::clear_trusted_accounts(1))
+        );
+    });
+}
+
+#[test]
+fn clear_trusted_accounts_over_long_list() {
+    build_and_execute(|| {
+        use crate::WeightInfo;
+
+        // A list longer than MaxTrustedAccounts, which could only have been left by a bug.
+        for (count, trustee) in (2..=6).enumerate() {
+            TemplateModule::insert_trust(&1, &trustee, count as u32);
+        }
+        let info = TemplateModule::clear_trusted_accounts(RuntimeOrigin::signed(1), 100).unwrap();
+        assert_eq!(
+            info.actual_weight,
+            Some(<() as WeightInfo>::clear_trusted_accounts(4))
+        );
+        System::assert_last_event(Event::TrustedAccountsCleared(1, 4, true).into());
+        assert_eq!(TemplateModule::trusted_by(1), vec![2]);
+    });
+}
+
+#[test]
+fn update_trust_level_not_trusted() {
+    build_and_execute(|| {
+        assert_noop!(
+            TemplateModule::update_trust_level(RuntimeOrigin::signed(1), 2, 1),
+            Error::<Test>::NotTrusted
+        );
+    });
+}
+
+#[test]
+fn update_trust_level() {
+    build_and_execute(|| {
+        assert_eq!(TemplateModule::trust_level(1, 2), None);
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_eq!(TemplateModule::trust_level(1, 2), Some(0));
+        System::assert_has_event(Event::AccountTrusted(1, 2, 0).into());
+
+        assert_ok!(TemplateModule::update_trust_level(
+            RuntimeOrigin::signed(1),
+            2,
+            2
+        ));
+        assert_eq!(TemplateModule::trust_level(1, 2), Some(2));
+        assert_eq!(TemplateModule::account_trust_level(1, 2), 2);
+        System::assert_last_event(Event::TrustLevelUpdated(1, 2, 2).into());
+
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 2));
+        assert_eq!(TemplateModule::trust_level(1, 2), None);
+        assert!(!crate::AccountTrustLevel::<Test>::contains_key(1, 2));
+    });
+}
+
+#[test]
+fn is_trusted_with_level() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::update_trust_level(
+            RuntimeOrigin::signed(1),
+            2,
+            2
+        ));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(2), 3));
+        assert_ok!(TemplateModule::update_trust_level(
+            RuntimeOrigin::signed(2),
+            3,
+            1
+        ));
+
+        assert!(TemplateModule::is_trusted_with_level(1, 2, 2));
+        assert!(!TemplateModule::is_trusted_with_level(1, 2, 3));
+        assert!(!TemplateModule::is_trusted_with_level(1, 3, 0));
+
+        assert!(TemplateModule::is_trusted_deep_with_level(1, 2, 2));
+        assert!(TemplateModule::is_trusted_deep_with_level(1, 3, 1));
+        assert!(!TemplateModule::is_trusted_deep_with_level(1, 3, 2));
+        assert!(TemplateModule::is_trusted_only_deep_with_level(1, 3, 1));
+        assert!(!TemplateModule::is_trusted_only_deep_with_level(1, 2, 0));
+    });
+}
+
+#[test]
+fn trust_account_with_expiry_in_past() {
+    build_and_execute(|| {
+        assert_noop!(
+            TemplateModule::trust_account_with_expiry(RuntimeOrigin::signed(1), 2, 1),
+            Error::<Test>::ExpiryInPast
+        );
+    });
+}
+
+#[test]
+fn trust_account_with_expiry() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            2,
+            5
+        ));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(2), 3));
+        assert_eq!(TemplateModule::account_trust_expiry(1, 2), Some(5));
+
+        System::set_block_number(4);
+        assert!(TemplateModule::is_trusted(1, 2));
+        assert!(TemplateModule::is_trusted_deep(1, 3));
+        assert_eq!(TemplateModule::trusted_by(1), vec![2]);
+
+        // Expired but not yet pruned.
+        System::set_block_number(5);
+        assert!(!TemplateModule::is_trusted(1, 2));
+        assert!(!TemplateModule::is_trusted_deep(1, 2));
+        assert!(!TemplateModule::is_trusted_deep(1, 3));
+        assert!(TemplateModule::trusted_by(1).is_empty());
+        assert_eq!(TemplateModule::trust_level(1, 2), None);
+        assert_eq!(TemplateModule::account_trusted_account_list_count(1), 1);
+
+        // Expired trust can be replaced before it is pruned.
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        System::assert_has_event(Event::TrustExpired(1, 2).into());
+        assert!(TemplateModule::is_trusted(1, 2));
+        assert_eq!(TemplateModule::account_trust_expiry(1, 2), None);
+        assert_eq!(TemplateModule::account_trusted_account_list_count(1), 1);
+    });
+}
+
+#[test]
+fn trust_weight_covers_replacing_expired_trust() {
+    use crate::WeightInfo;
+    use frame_support::dispatch::GetDispatchInfo;
+
+    let replacing = <() as WeightInfo>::trust_account_replacing_expired();
+    let weight = crate::Call::<Test>::trust_account { account: 2 }
+        .get_dispatch_info()
+        .weight;
+    assert_eq!(weight, <() as WeightInfo>::trust_account().max(replacing));
+    assert!(weight.all_gte(replacing));
+
+    let weight = crate::Call::<Test>::force_trust {
+        truster: 1,
+        trustee: 2,
+    }
+    .get_dispatch_info()
+    .weight;
+    assert!(weight.all_gte(<() as WeightInfo>::force_trust_replacing_expired()));
+
+    let weight = crate::Call::<Test>::trust_account_with_expiry {
+        account: 2,
+        expires_at: 10,
+    }
+    .get_dispatch_info()
+    .weight;
+    assert!(weight.all_gte(replacing));
+}
+
+#[test]
+fn on_idle_prunes_expired_trust() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            2,
+            3
+        ));
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            3,
+            4
+        ));
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(4),
+            3,
+            3
+        ));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 4));
+        // Untrusted before expiry, so must not be pruned.
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(4), 3));
+
+        TemplateModule::on_idle(2, Weight::MAX);
+        assert_eq!(TemplateModule::account_trusted_account_list_count(1), 3);
+
+        TemplateModule::on_idle(3, Weight::MAX);
+        assert_eq!(TemplateModule::trusted_by(1), vec![4, 3]);
+        assert_eq!(TemplateModule::account_trusted_account_index(1, 2), None);
+        assert_eq!(TemplateModule::account_trust_expiry(1, 2), None);
+        assert!(TemplateModule::trusting(2).is_empty());
+        System::assert_has_event(Event::TrustExpired(1, 2).into());
+        System::assert_has_event(Event::TrustDepositReleased(1, 10).into());
+
+        TemplateModule::on_idle(10, Weight::MAX);
+        assert_eq!(TemplateModule::trusted_by(1), vec![4]);
+        assert!(TemplateModule::trusting(3).is_empty());
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::TrustDeposit.into(), &1),
+            10
+        );
+        assert_eq!(crate::TrustExpiryQueue::<Test>::iter().count(), 0);
+        // The cursor is cleared once nothing remains queued.
+        assert_eq!(crate::NextTrustExpiryBlock::<Test>::get(), None);
+        assert_eq!(
+            TemplateModule::on_idle(11, Weight::MAX),
+            RocksDbWeight::get().reads(3)
+        );
+    });
+}
+
+#[test]
+fn on_idle_drops_expiry_of_untrusted_pair() {
+    build_and_execute(|| {
+        // An expiry queued for a pair that is not trusted must not panic on_idle.
+        crate::AccountTrustExpiry::<Test>::insert(1, 2, 3);
+        crate::TrustExpiryQueue::<Test>::insert(3, (1, 2), ());
+        crate::NextTrustExpiryBlock::<Test>::put(3);
+
+        TemplateModule::on_idle(3, Weight::MAX);
+        assert_eq!(TemplateModule::account_trust_expiry(1, 2), None);
+        assert_eq!(crate::TrustExpiryQueue::<Test>::iter().count(), 0);
+        assert_eq!(crate::NextTrustExpiryBlock::<Test>::get(), None);
+        assert!(!System::events()
+            .iter()
+            .any(|record| record.event == Event::TrustExpired(1, 2).into()));
+    });
+}
+
+#[test]
+fn on_idle_keeps_expiry_cursor_while_trust_is_queued() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            2,
+            3
+        ));
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            3,
+            20
+        ));
+
+        TemplateModule::on_idle(5, Weight::MAX);
+        assert_eq!(TemplateModule::trusted_by(1), vec![3]);
+        assert_eq!(crate::NextTrustExpiryBlock::<Test>::get(), Some(6));
+
+        // Trust added after the cursor was cleared sets it again.
+        TemplateModule::on_idle(20, Weight::MAX);
+        assert_eq!(crate::NextTrustExpiryBlock::<Test>::get(), None);
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            4,
+            30
+        ));
+        assert_eq!(crate::NextTrustExpiryBlock::<Test>::get(), Some(30));
+    });
+}
+
+#[test]
+fn on_idle_respects_weight_limit() {
+    build_and_execute(|| {
+        use crate::WeightInfo;
+
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            2,
+            3
+        ));
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            3,
+            3
+        ));
+
+        let limit = <() as WeightInfo>::expire_trust() * 3 / 2;
+        let used = TemplateModule::on_idle(3, limit);
+        assert!(used.all_lte(limit));
+        assert_eq!(TemplateModule::account_trusted_account_list_count(1), 1);
+        assert_eq!(crate::NextTrustExpiryBlock::<Test>::get(), Some(3));
+
+        TemplateModule::on_idle(3, limit);
+        assert_eq!(TemplateModule::account_trusted_account_list_count(1), 0);
+    });
+}
+
+#[test]
+fn trust_path() {
+    build_and_execute(|| {
+        // 1 -> 2 -> 3 -> 4 -> 5 and a shortcut 1 -> 6 -> 4.
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(2), 3));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(3), 4));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(4), 5));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 6));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(6), 4));
+
+        assert_eq!(TemplateModule::trust_path(1, 1, 0, 0), Some(vec![1]));
+        assert_eq!(TemplateModule::trust_path(1, 2, 1, 10), Some(vec![1, 2]));
+        assert_eq!(TemplateModule::trust_path(1, 3, 2, 10), Some(vec![1, 2, 3]));
+        assert_eq!(
+            TemplateModule::trust_path(1, 5, 3, 10),
+            Some(vec![1, 6, 4, 5])
+        );
+        assert_eq!(TemplateModule::trust_path(1, 5, 2, 10), None);
+        assert_eq!(TemplateModule::trust_path(5, 1, 10, 10), None);
+        // 1, 2, 6, 3 and 4 have to be expanded to reach 5.
+        assert_eq!(TemplateModule::trust_path(1, 5, 3, 4), None);
+        assert_eq!(
+            TemplateModule::trust_path(1, 5, 3, 5),
+            Some(vec![1, 6, 4, 5])
+        );
+    });
+}
+
+#[test]
+fn trust_path_ignores_expired_trust() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            2,
+            5
+        ));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(2), 3));
+        assert_eq!(TemplateModule::trust_path(1, 3, 2, 10), Some(vec![1, 2, 3]));
+
+        System::set_block_number(5);
+        assert_eq!(TemplateModule::trust_path(1, 3, 2, 10), None);
+    });
+}
+
+#[test]
+fn trusted_by_paged() {
+    build_and_execute(|| {
+        assert_eq!(TemplateModule::trusted_by_paged(1, 0, 2), (vec![], 0));
+
+        assert_ok!(TemplateModule::trust_accounts(
+            RuntimeOrigin::signed(1),
+            vec![2, 3, 4].try_into().unwrap()
+        ));
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            5,
+            5
+        ));
+
+        assert_eq!(TemplateModule::trusted_by_paged(1, 0, 2), (vec![2, 3], 4));
+        assert_eq!(TemplateModule::trusted_by_paged(1, 2, 2), (vec![4, 5], 4));
+        assert_eq!(TemplateModule::trusted_by_paged(1, 4, 2), (vec![], 4));
+        assert_eq!(
+            TemplateModule::trusted_by_paged(1, 1, u32::MAX),
+            (vec![3, 4, 5], 4)
+        );
+
+        System::set_block_number(5);
+        assert_eq!(TemplateModule::trusted_by_paged(1, 2, 2), (vec![4], 4));
+    });
+}
+
+#[test]
+fn trust_changes() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(3), 1));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(3), 4));
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 2));
+
+        assert_eq!(
+            TemplateModule::trust_changes(1),
+            vec![(1, 2, true), (3, 1, true), (1, 2, false)]
+        );
+        assert_eq!(TemplateModule::trust_changes(4), vec![(3, 4, true)]);
+        assert!(TemplateModule::trust_changes(5).is_empty());
+        assert_eq!(
+            TemplateModule::all_trust_changes(),
+            vec![(1, 2, true), (3, 1, true), (3, 4, true), (1, 2, false)]
+        );
+    });
+}
+
+#[test]
+fn genesis_config() {
+    new_test_ext_with_trusts(vec![(1, 2), (1, 3), (2, 3)]).execute_with(|| {
+        assert_eq!(TemplateModule::trusted_by(1), vec![2, 3]);
+        assert_eq!(TemplateModule::trusted_by(2), vec![3]);
+        assert_eq!(TemplateModule::account_trusted_account_index(1, 3), Some(2));
+        assert_eq!(TemplateModule::trusting(3), vec![1, 2]);
+        assert_eq!(
+            TemplateModule::account_trusting_account_index(3, 2),
+            Some(2)
+        );
+        assert!(TemplateModule::is_trusted_deep(1, 3));
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(1), 2));
+        assert_eq!(TemplateModule::trusted_by(1), vec![3]);
+        assert_ok!(TemplateModule::do_try_state());
+    });
+}
+
+#[test]
+#[should_panic(expected = "Genesis trust pair trusts self")]
+fn genesis_config_trust_self() {
+    new_test_ext_with_trusts(vec![(1, 2), (3, 3)]);
+}
+
+#[test]
+#[should_panic(expected = "Duplicate genesis trust pair")]
+fn genesis_config_duplicate() {
+    new_test_ext_with_trusts(vec![(1, 2), (1, 2)]);
+}
+
+#[test]
+#[should_panic(expected = "Genesis trust pairs exceed MaxTrustedAccounts")]
+fn genesis_config_too_many_trusted_accounts() {
+    new_test_ext_with_trusts(vec![(1, 2), (1, 3), (1, 4), (1, 5), (1, 6)]);
+}
+
+#[test]
+fn try_state_detects_corruption() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 3));
+        assert_ok!(TemplateModule::do_try_state());
+        AccountTrustedAccountIndex::<Test>::insert(1, 3, 1);
+        assert!(TemplateModule::do_try_state().is_err());
+        AccountTrustedAccountIndex::<Test>::insert(1, 3, 2);
+        AccountTrustingAccountListCount::<Test>::insert(3, 2);
+        assert!(TemplateModule::do_try_state().is_err());
+    });
+}
+
+#[test]
+fn force_trust_bad_origin() {
+    build_and_execute(|| {
+        assert_noop!(
+            TemplateModule::force_trust(RuntimeOrigin::signed(1), 1, 2),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            TemplateModule::force_untrust(RuntimeOrigin::signed(1), 1, 2),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn force_trust() {
+    build_and_execute(|| {
+        // Account 10 has no funds, so no deposit can be held.
+        assert_ok!(TemplateModule::force_trust(RuntimeOrigin::root(), 10, 2));
+        assert!(TemplateModule::is_trusted(10, 2));
+        assert_eq!(TemplateModule::trusting(2), vec![10]);
+        System::assert_last_event(Event::AccountForceTrusted(10, 2).into());
+
+        assert_noop!(
+            TemplateModule::force_trust(RuntimeOrigin::root(), 10, 2),
+            Error::<Test>::AlreadyTrusted
+        );
+        assert_noop!(
+            TemplateModule::force_trust(RuntimeOrigin::root(), 10, 10),
+            Error::<Test>::TrustSelf
+        );
+    });
+}
+
+#[test]
+fn force_untrust() {
+    build_and_execute(|| {
+        let reason = HoldReason::TrustDeposit.into();
+
+        assert_noop!(
+            TemplateModule::force_untrust(RuntimeOrigin::root(), 1, 2),
+            Error::<Test>::NotTrusted
+        );
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::force_untrust(RuntimeOrigin::root(), 1, 2));
+        assert!(!TemplateModule::is_trusted(1, 2));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 0);
+        System::assert_last_event(Event::AccountForceUntrusted(1, 2).into());
+        assert_eq!(
+            TemplateModule::trust_changes(1),
+            vec![(1, 2, true), (1, 2, false)]
+        );
+    });
+}
+
+#[test]
+fn purge_trustee() {
+    build_and_execute(|| {
+        let reason = HoldReason::TrustDeposit.into();
+
+        for truster in 1..=5 {
+            assert_ok!(TemplateModule::trust_account(
+                RuntimeOrigin::signed(truster),
+                9
+            ));
+            assert_ok!(TemplateModule::trust_account(
+                RuntimeOrigin::signed(truster),
+                8
+            ));
+        }
+        assert_noop!(
+            TemplateModule::purge_trustee(RuntimeOrigin::signed(1), 9, 5),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TemplateModule::purge_trustee(RuntimeOrigin::root(), 9, 3));
+        System::assert_last_event(Event::TrustingAccountsPurged(9, 3, true).into());
+        assert_eq!(TemplateModule::trusting(9), vec![1, 2]);
+        assert!(!TemplateModule::is_trusted(5, 9));
+        assert_eq!(TemplateModule::trusted_by(5), vec![8]);
+        assert_eq!(Balances::balance_on_hold(&reason, &5), 10);
+
+        assert_ok!(TemplateModule::purge_trustee(RuntimeOrigin::root(), 9, 3));
+        System::assert_last_event(Event::TrustingAccountsPurged(9, 2, false).into());
+        assert!(TemplateModule::trusting(9).is_empty());
+        assert_eq!(TemplateModule::trusting(8), vec![1, 2, 3, 4, 5]);
+    });
+}
+
+#[test]
+fn purge_trustee_refunds_weight() {
+    build_and_execute(|| {
+        use crate::WeightInfo;
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 9));
+        let info = TemplateModule::purge_trustee(RuntimeOrigin::root(), 9, 10).unwrap();
+        assert_eq!(
+            info.actual_weight,
+            Some(<() as WeightInfo>::purge_trustee(1))
+        );
+    });
+}
+
+#[test]
+fn purge_trustee_weight_is_capped() {
+    use crate::WeightInfo;
+    use frame_support::dispatch::GetDispatchInfo;
+
+    let weight = crate::Call::<Test>::purge_trustee {
+        account: 9,
+        limit: u32::MAX,
+    }
+    .get_dispatch_info()
+    .weight;
+    assert_eq!(
+        weight,
+        <() as WeightInfo>::purge_trustee(crate::MAX_PURGE_TRUSTEE_LIMIT)
+    );
+}
+
+#[test]
+fn on_killed_account() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::force_trust(RuntimeOrigin::root(), 1, 2));
+        assert_ok!(TemplateModule::force_trust(RuntimeOrigin::root(), 1, 3));
+        assert_ok!(TemplateModule::force_trust(RuntimeOrigin::root(), 4, 2));
+
+        // Reap account 1 by transferring its whole balance away.
+        assert_ok!(Balances::transfer_all(RuntimeOrigin::signed(1), 5, false));
+        assert!(!System::account_exists(&1));
+        // The trust is only cleared on_idle.
+        assert!(crate::KilledAccountQueue::<Test>::contains_key(1));
+        assert_eq!(TemplateModule::trusted_by(1), vec![2, 3]);
+
+        TemplateModule::on_idle(System::block_number(), Weight::MAX);
+        System::assert_has_event(Event::TrustedAccountsCleared(1, 2, false).into());
+        assert!(!crate::KilledAccountQueue::<Test>::contains_key(1));
+        assert!(TemplateModule::trusted_by(1).is_empty());
+        assert!(!AccountTrustedAccountListCount::<Test>::contains_key(1));
+        assert_eq!(TemplateModule::trusting(2), vec![4]);
+        assert!(TemplateModule::trusting(3).is_empty());
+    });
+}
+
+#[test]
+fn on_killed_account_respects_weight_limit() {
+    build_and_execute(|| {
+        use crate::WeightInfo;
+
+        assert_ok!(TemplateModule::force_trust(RuntimeOrigin::root(), 1, 2));
+        assert_ok!(TemplateModule::force_trust(RuntimeOrigin::root(), 1, 3));
+        assert_ok!(TemplateModule::force_trust(RuntimeOrigin::root(), 1, 4));
+        assert_ok!(Balances::transfer_all(RuntimeOrigin::signed(1), 5, false));
+
+        // Enough weight for the queue and two trust pairs.
+        let limit = RocksDbWeight::get()
+            .reads_writes(3, 2)
+            .saturating_add(<() as WeightInfo>::expire_trust().saturating_mul(2));
+        let used = TemplateModule::clear_killed_accounts(limit);
+        assert!(used.all_lte(limit));
+        System::assert_has_event(Event::TrustedAccountsCleared(1, 2, true).into());
+        assert_eq!(TemplateModule::trusted_by(1), vec![2]);
+        assert!(crate::KilledAccountQueue::<Test>::contains_key(1));
+
+        TemplateModule::clear_killed_accounts(limit);
+        System::assert_has_event(Event::TrustedAccountsCleared(1, 1, false).into());
+        assert!(TemplateModule::trusted_by(1).is_empty());
+        assert!(!crate::KilledAccountQueue::<Test>::contains_key(1));
+    });
+}
+
+#[test]
+fn on_killed_account_keeps_trust_of_recreated_account() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::force_trust(RuntimeOrigin::root(), 1, 2));
+        assert_ok!(Balances::transfer_all(RuntimeOrigin::signed(1), 5, false));
+        // Account 1 is funded again before on_idle runs.
+        assert_ok!(Balances::transfer_allow_death(
+            RuntimeOrigin::signed(5),
+            1,
+            50
+        ));
+
+        TemplateModule::on_idle(System::block_number(), Weight::MAX);
+        assert!(!crate::KilledAccountQueue::<Test>::contains_key(1));
+        assert_eq!(TemplateModule::trusted_by(1), vec![2]);
+    });
+}
+
+#[test]
+fn on_killed_account_waits_for_migration() {
+    build_and_execute(|| {
+        use migrations::v1::v0;
+
+        StorageVersion::new(0).put::<TemplateModule>();
+        v0::AccountTrustedAccountListCount::<Test>::insert(1, 1);
+        v0::AccountTrustedAccountList::<Test>::insert(1, 0, 2);
+        v0::AccountTrustedAccountIndex::<Test>::insert(1, 2, 1);
+        migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        assert_ok!(Balances::transfer_all(RuntimeOrigin::signed(1), 5, false));
+        assert!(crate::KilledAccountQueue::<Test>::contains_key(1));
+
+        // The migration finishes before the queue is processed.
+        run_migration(Weight::MAX);
+        assert!(!crate::MigrationInProgress::<Test>::exists());
+        assert_eq!(TemplateModule::trusted_by(1), vec![2]);
+
+        TemplateModule::on_idle(System::block_number(), Weight::MAX);
+        assert!(TemplateModule::trusted_by(1).is_empty());
+        assert!(!crate::KilledAccountQueue::<Test>::contains_key(1));
+    });
+}
+
+#[test]
+fn trust_provider() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(2), 3));
+
+        assert!(<TemplateModule as TrustProvider<u64>>::is_trusted(&1, &2));
+        assert!(!<TemplateModule as TrustProvider<u64>>::is_trusted(&1, &3));
+        assert!(<TemplateModule as TrustProvider<u64>>::is_trusted_only_deep(&1, &3));
+        assert!(<TemplateModule as TrustProvider<u64>>::is_trusted_deep(
+            &1, &3
+        ));
+        assert_eq!(
+            <TemplateModule as TrustProvider<u64>>::trust_path(&1, &3, 2, 10),
+            Some(vec![1, 2, 3])
+        );
+
+        assert!(!<() as TrustProvider<u64>>::is_trusted(&1, &2));
+        assert!(!<() as TrustProvider<u64>>::is_trusted_deep(&1, &3));
+        assert_eq!(<() as TrustProvider<u64>>::trust_path(&1, &3, 2, 10), None);
+    });
+}
+
+frame_support::parameter_types! {
+    pub const Curator: u64 = 1;
+}
+
+#[test]
+fn ensure_trusted_by() {
+    build_and_execute(|| {
+        type Origin = EnsureTrustedBy<Test, Curator>;
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(2), 3));
+
+        assert_eq!(Origin::try_origin(RuntimeOrigin::signed(2)).ok(), Some(2));
+        assert!(Origin::try_origin(RuntimeOrigin::signed(3)).is_err());
+        assert!(Origin::try_origin(RuntimeOrigin::signed(1)).is_err());
+        assert!(Origin::try_origin(RuntimeOrigin::root()).is_err());
+    });
+}
+
+#[test]
+fn ensure_trusted_deep_by() {
+    build_and_execute(|| {
+        type Origin = EnsureTrustedDeepBy<Test, Curator>;
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(2), 3));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(3), 4));
+
+        assert_eq!(Origin::try_origin(RuntimeOrigin::signed(2)).ok(), Some(2));
+        assert_eq!(Origin::try_origin(RuntimeOrigin::signed(3)).ok(), Some(3));
+        assert!(Origin::try_origin(RuntimeOrigin::signed(4)).is_err());
+        assert!(Origin::try_origin(RuntimeOrigin::none()).is_err());
+    });
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+#[test]
+fn ensure_trusted_by_successful_origin() {
+    build_and_execute(|| {
+        let origin = EnsureTrustedBy::<Test, Curator>::try_successful_origin().unwrap();
+        assert_ok!(EnsureTrustedBy::<Test, Curator>::try_origin(origin));
+        let origin = EnsureTrustedDeepBy::<Test, Curator>::try_successful_origin().unwrap();
+        assert_ok!(EnsureTrustedDeepBy::<Test, Curator>::try_origin(origin));
+        // The worst case: the intermediary is last in a full list.
+        let trusted = TemplateModule::trusted_by(Curator::get());
+        assert_eq!(trusted.len(), 4);
+        assert_eq!(
+            trusted.last(),
+            Some(&frame_benchmarking::account("intermediary", 0, 0))
+        );
+    });
+}
+
+#[test]
+fn check_trusted_sender() {
+    build_and_execute(|| {
+        let info = Default::default();
+        let check =
+            |who: u64, call: &RuntimeCall| CheckTrusted::new().validate(&who, call, &info, 0);
+        let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let untrusted = InvalidTransaction::Custom(crate::extension::UNTRUSTED_SENDER).into();
+
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 2));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(2), 3));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(3), 4));
+
+        // The root and accounts within two hops of it are allowed.
+        assert_ok!(check(1, &call));
+        assert_ok!(check(2, &call));
+        assert_ok!(check(3, &call));
+        assert_eq!(check(4, &call), Err(untrusted));
+        assert_eq!(
+            CheckTrusted::new().pre_dispatch(&4, &call, &info, 0),
+            Err(untrusted)
+        );
+
+        // Exempt calls are allowed from any account.
+        let call = RuntimeCall::TemplateModule(crate::Call::trust_account { account: 5 });
+        assert_ok!(check(4, &call));
+    });
+}
+
+#[test]
+fn check_trusted_sender_is_bounded() {
+    build_and_execute(|| {
+        use frame_support::dispatch::DispatchClass;
+
+        let info = Default::default();
+        let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let untrusted = InvalidTransaction::Custom(crate::extension::UNTRUSTED_SENDER).into();
+        let block_weight = || *System::block_weight().get(DispatchClass::Normal);
+
+        // Only the first two roots are checked.
+        TrustRoots::set(&vec![5, 6, 1]);
+        assert_ok!(CheckTrusted::new().validate(&6, &call, &info, 0));
+        assert_eq!(
+            CheckTrusted::new().validate(&1, &call, &info, 0),
+            Err(untrusted)
+        );
+
+        // The worst case weight of checking each root is registered.
+        let before = block_weight();
+        assert_ok!(CheckTrusted::new().pre_dispatch(&5, &call, &info, 0));
+        assert_eq!(
+            block_weight(),
+            before + TemplateModule::is_trusted_deep_weight() * 2
+        );
+
+        // Exempt calls check no roots.
+        let before = block_weight();
+        let call = RuntimeCall::TemplateModule(crate::Call::trust_account { account: 5 });
+        assert_ok!(CheckTrusted::new().pre_dispatch(&9, &call, &info, 0));
+        assert_eq!(block_weight(), before);
+    });
+}
+
+#[test]
+fn prioritize_trusted_sender() {
+    build_and_execute(|| {
+        let info = Default::default();
+        let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let priority = |who: u64| {
+            PrioritizeTrusted::new()
+                .validate(&who, &call, &info, 0)
+                .unwrap()
+                .priority
+        };
+
+        assert_eq!(priority(9), 0);
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 9));
+        assert_eq!(priority(9), 100);
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(3), 9));
+        assert_eq!(priority(9), 200);
+        // Only the first three anchors are checked.
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(4), 9));
+        assert_eq!(priority(9), 200);
+        // Trust from accounts that are not anchors does not count.
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(5), 9));
+        assert_eq!(priority(9), 200);
+        assert_eq!(TemplateModule::trust_score(&9, &[1, 2, 3, 4, 5]), 4);
+    });
+}
+
+#[test]
+fn prioritize_trusted_sender_registers_weight() {
+    build_and_execute(|| {
+        use crate::WeightInfo;
+        use frame_support::dispatch::DispatchClass;
+
+        let info = Default::default();
+        let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        let block_weight = || *System::block_weight().get(DispatchClass::Normal);
+
+        // Only the first three anchors are looked up.
+        let before = block_weight();
+        assert_ok!(PrioritizeTrusted::new().pre_dispatch(&9, &call, &info, 0));
+        assert_eq!(block_weight(), before + <() as WeightInfo>::trust_score(3));
+    });
+}
+
+#[test]
+fn trust_graph() {
+    new_test_ext_with_trusts(vec![(1, 2), (1, 3), (2, 3), (4, 1)]).execute_with(|| {
+        let graph = TemplateModule::trust_graph(None, 10);
+        assert_eq!(graph.len(), 3);
+        assert!(graph.contains(&(1, vec![2, 3])));
+        assert!(graph.contains(&(2, vec![3])));
+        assert!(graph.contains(&(4, vec![1])));
+
+        // Page through the graph one truster at a time.
+        let mut pages = vec![];
+        let mut start = None;
+        loop {
+            let page = TemplateModule::trust_graph(start, 1);
+            match page.last() {
+                Some((truster, _)) => start = Some(*truster),
+                None => break,
+            }
+            pages.extend(page);
+        }
+        assert_eq!(pages, graph);
+
+        // Accounts that no longer trust any others are skipped.
+        assert_ok!(TemplateModule::untrust_account(RuntimeOrigin::signed(2), 3));
+        assert_eq!(TemplateModule::trust_graph(None, 10).len(), 2);
+        assert_ok!(TemplateModule::do_try_state());
+    });
+}
+
+#[test]
+fn trust_expiries() {
+    build_and_execute(|| {
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            2,
+            5
+        ));
+        assert_ok!(TemplateModule::trust_account(RuntimeOrigin::signed(1), 3));
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(1),
+            4,
+            3
+        ));
+        assert_ok!(TemplateModule::trust_account_with_expiry(
+            RuntimeOrigin::signed(2),
+            3,
+            4
+        ));
+
+        let mut expiries = TemplateModule::trust_expiries(1);
+        expiries.sort();
+        assert_eq!(expiries, vec![(2, 5), (4, 3)]);
+        assert_eq!(TemplateModule::trust_expiries(3), vec![]);
+
+        // Expired pairs are included until they are pruned.
+        System::set_block_number(3);
+        assert_eq!(TemplateModule::trust_expiries(1).len(), 2);
+        TemplateModule::on_idle(3, Weight::MAX);
+        assert_eq!(TemplateModule::trust_expiries(1), vec![(2, 5)]);
+    });
+}
+
+#[test]
+fn snapshot_round_trip() {
+    use codec::{Decode, Encode};
+    use pallet_acuity_trusted_accounts_rpc_runtime_api::snapshot::{
+        Snapshot, SnapshotError, SNAPSHOT_VERSION,
+    };
+    use sp_core::H256;
+
+    let trusts = vec![(1, 2), (1, 3), (2, 3), (3, 1), (1, 4), (4, 2)];
+    let (graph, trusted) = new_test_ext_with_trusts(trusts).execute_with(|| {
+        assert_ok!(TemplateModule::do_try_state());
+        let graph = TemplateModule::trust_graph(None, 100);
+        let trusted: Vec<_> = (1..=4).map(TemplateModule::trusted_by).collect();
+        (graph, trusted)
+    });
+    let snapshot = Snapshot::new(H256::repeat_byte(1), graph);
+    assert_eq!(snapshot.header.version, SNAPSHOT_VERSION);
+    assert_eq!(snapshot.header.trusters, 4);
+    assert_eq!(snapshot.header.trust_pairs, 6);
+    assert_eq!(snapshot.validate(), Ok(()));
+
+    // SCALE
+    let encoded = snapshot.encode();
+    assert_eq!(u32::decode(&mut &encoded[..]), Ok(SNAPSHOT_VERSION));
+    let decoded = Snapshot::<u64, H256>::decode(&mut &encoded[..]).unwrap();
+    assert_eq!(decoded, snapshot);
+
+    // JSON
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert!(json.starts_with(r#"{"header":{"version":1,"blockHash":"#));
+    let decoded: Snapshot<u64, H256> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, snapshot);
+
+    // Import into a new chain.
+    new_test_ext_with_trusts(decoded.into_pairs()).execute_with(|| {
+        let imported: Vec<_> = (1..=4).map(TemplateModule::trusted_by).collect();
+        assert_eq!(imported, trusted);
+        assert_ok!(TemplateModule::do_try_state());
+    });
+
+    let mut invalid = snapshot.clone();
+    invalid.header.trust_pairs = 5;
+    assert_eq!(invalid.validate(), Err(SnapshotError::SizeMismatch));
+    invalid.header.version = 2;
+    assert_eq!(
+        invalid.validate(),
+        Err(SnapshotError::UnsupportedVersion(2))
+    );
+}
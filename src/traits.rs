@@ -0,0 +1,72 @@
+//! Traits for other pallets to query the trust graph without depending on this pallet.
+
+use crate::{Config, Pallet};
+use sp_std::prelude::Vec;
+
+/// Queries on a trust graph.
+pub trait TrustProvider<AccountId> {
+    /// Whether `truster` trusts `trustee` directly.
+    fn is_trusted(truster: &AccountId, trustee: &AccountId) -> bool;
+
+    /// Whether `truster` trusts an account that trusts `trustee`.
+    fn is_trusted_only_deep(truster: &AccountId, trustee: &AccountId) -> bool;
+
+    /// Whether `truster` trusts `trustee` directly or through an account it trusts.
+    fn is_trusted_deep(truster: &AccountId, trustee: &AccountId) -> bool;
+
+    /// The shortest chain of trust from `from` to `to`, including both, searching at most
+    /// `max_depth` hops and expanding at most `max_nodes` accounts.
+    fn trust_path(
+        from: &AccountId,
+        to: &AccountId,
+        max_depth: u32,
+        max_nodes: u32,
+    ) -> Option<Vec<AccountId>>;
+}
+
+impl<T: Config> TrustProvider<T::AccountId> for Pallet<T> {
+    fn is_trusted(truster: &T::AccountId, trustee: &T::AccountId) -> bool {
+        Pallet::<T>::is_trusted(truster.clone(), trustee.clone())
+    }
+
+    fn is_trusted_only_deep(truster: &T::AccountId, trustee: &T::AccountId) -> bool {
+        Pallet::<T>::is_trusted_only_deep(truster.clone(), trustee.clone())
+    }
+
+    fn is_trusted_deep(truster: &T::AccountId, trustee: &T::AccountId) -> bool {
+        Pallet::<T>::is_trusted_deep(truster.clone(), trustee.clone())
+    }
+
+    fn trust_path(
+        from: &T::AccountId,
+        to: &T::AccountId,
+        max_depth: u32,
+        max_nodes: u32,
+    ) -> Option<Vec<T::AccountId>> {
+        Pallet::<T>::trust_path(from.clone(), to.clone(), max_depth, max_nodes)
+    }
+}
+
+/// An empty trust graph, in which no account trusts any other.
+impl<AccountId> TrustProvider<AccountId> for () {
+    fn is_trusted(_truster: &AccountId, _trustee: &AccountId) -> bool {
+        false
+    }
+
+    fn is_trusted_only_deep(_truster: &AccountId, _trustee: &AccountId) -> bool {
+        false
+    }
+
+    fn is_trusted_deep(_truster: &AccountId, _trustee: &AccountId) -> bool {
+        false
+    }
+
+    fn trust_path(
+        _from: &AccountId,
+        _to: &AccountId,
+        _max_depth: u32,
+        _max_nodes: u32,
+    ) -> Option<Vec<AccountId>> {
+        None
+    }
+}
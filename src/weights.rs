@@ -0,0 +1,515 @@
+//! Placeholder weights for pallet_acuity_trusted_accounts
+//!
+//! THESE WEIGHTS ARE NOT BENCHMARK OUTPUT. They are hand-written estimates: the storage items
+//! listed for each function and their read and write counts are taken from the code, but the
+//! base and per-item execution times are guesses and have not been measured on any hardware.
+//! Functions that take a count charge every storage access made per item once for each item,
+//! including the `MigrationInProgress` check each item repeats.
+//!
+//! Runtimes must not use `SubstrateWeight` in production. Generate real weights for the target
+//! runtime with the benchmarks in `benchmarking.rs`:
+//!
+//! ```text
+//! node benchmark pallet --pallet=pallet_acuity_trusted_accounts --extrinsic=* \
+//!     --steps=50 --repeat=20 --output=./src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_acuity_trusted_accounts.
+pub trait WeightInfo {
+	fn trust_account() -> Weight;
+	fn trust_account_replacing_expired() -> Weight;
+	fn untrust_account(l: u32, ) -> Weight;
+	fn trust_accounts(n: u32, ) -> Weight;
+	fn untrust_accounts(n: u32, ) -> Weight;
+	fn clear_trusted_accounts(n: u32, ) -> Weight;
+	fn update_trust_level() -> Weight;
+	fn trust_account_with_expiry() -> Weight;
+	fn expire_trust() -> Weight;
+	fn force_trust() -> Weight;
+	fn force_trust_replacing_expired() -> Weight;
+	fn force_untrust() -> Weight;
+	fn purge_trustee(n: u32, ) -> Weight;
+	fn trust_score(n: u32, ) -> Weight;
+	fn is_trusted_deep(n: u32, ) -> Weight;
+}
+
+/// Placeholder weights for pallet_acuity_trusted_accounts, estimated by hand rather than measured.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:0 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn trust_account() -> Weight {
+		Weight::from_parts(49_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:2)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn trust_account_replacing_expired() -> Weight {
+		Weight::from_parts(98_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(24_u64))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:1 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn untrust_account(l: u32, ) -> Weight {
+		Weight::from_parts(53_000_000, 3593)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:n w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:n w:n)
+	/// Storage: `Balances::Holds` (r:n w:n)
+	/// Storage: `System::Account` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:0 w:n)
+	fn trust_accounts(n: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 3593)
+			.saturating_add(Weight::from_parts(49_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((10_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:n w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:n)
+	/// Storage: `Balances::Holds` (r:n w:n)
+	/// Storage: `System::Account` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:n w:n)
+	fn untrust_accounts(n: u32, ) -> Weight {
+		Weight::from_parts(13_000_000, 3593)
+			.saturating_add(Weight::from_parts(53_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((11_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((15_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:n+1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:n+1 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:2n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:n)
+	/// Storage: `Balances::Holds` (r:n w:n)
+	/// Storage: `System::Account` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:n w:n)
+	fn clear_trusted_accounts(n: u32, ) -> Weight {
+		Weight::from_parts(11_000_000, 3593)
+			.saturating_add(Weight::from_parts(55_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((12_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((15_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn update_trust_level() -> Weight {
+		Weight::from_parts(14_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::NextTrustExpiryBlock` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:1)
+	/// Storage: `TrustedAccounts::TrustExpiryQueue` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:0 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn trust_account_with_expiry() -> Weight {
+		Weight::from_parts(55_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	/// Storage: `TrustedAccounts::TrustExpiryQueue` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:1 w:1)
+	fn expire_trust() -> Weight {
+		Weight::from_parts(56_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(16_u64))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:0 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn force_trust() -> Weight {
+		Weight::from_parts(21_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn force_trust_replacing_expired() -> Weight {
+		Weight::from_parts(72_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(21_u64))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:1 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn force_untrust() -> Weight {
+		Weight::from_parts(53_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:n+1 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:2n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:n)
+	/// Storage: `Balances::Holds` (r:n w:n)
+	/// Storage: `System::Account` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:n w:n)
+	fn purge_trustee(n: u32, ) -> Weight {
+		Weight::from_parts(11_000_000, 3593)
+			.saturating_add(Weight::from_parts(55_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((11_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((15_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:n w:0)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:n w:0)
+	fn trust_score(n: u32, ) -> Weight {
+		Weight::from_parts(2_000_000, 0)
+			.saturating_add(Weight::from_parts(7_000_000, 3593).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2n+1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:n+1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:n w:0)
+	fn is_trusted_deep(n: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 3593)
+			.saturating_add(Weight::from_parts(14_000_000, 2500).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests. These are the same placeholder estimates.
+impl WeightInfo for () {
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:0 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn trust_account() -> Weight {
+		Weight::from_parts(49_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:2)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn trust_account_replacing_expired() -> Weight {
+		Weight::from_parts(98_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(24_u64))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:1 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn untrust_account(l: u32, ) -> Weight {
+		Weight::from_parts(53_000_000, 3593)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:n w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:n w:n)
+	/// Storage: `Balances::Holds` (r:n w:n)
+	/// Storage: `System::Account` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:0 w:n)
+	fn trust_accounts(n: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 3593)
+			.saturating_add(Weight::from_parts(49_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((10_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:n w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:n)
+	/// Storage: `Balances::Holds` (r:n w:n)
+	/// Storage: `System::Account` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:n w:n)
+	fn untrust_accounts(n: u32, ) -> Weight {
+		Weight::from_parts(13_000_000, 3593)
+			.saturating_add(Weight::from_parts(53_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((11_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((15_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:n+1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:n+1 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:2n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:n)
+	/// Storage: `Balances::Holds` (r:n w:n)
+	/// Storage: `System::Account` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:n w:n)
+	fn clear_trusted_accounts(n: u32, ) -> Weight {
+		Weight::from_parts(11_000_000, 3593)
+			.saturating_add(Weight::from_parts(55_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((12_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((15_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn update_trust_level() -> Weight {
+		Weight::from_parts(14_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::NextTrustExpiryBlock` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:1)
+	/// Storage: `TrustedAccounts::TrustExpiryQueue` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:0 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn trust_account_with_expiry() -> Weight {
+		Weight::from_parts(55_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	/// Storage: `TrustedAccounts::TrustExpiryQueue` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:1 w:1)
+	fn expire_trust() -> Weight {
+		Weight::from_parts(56_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:0 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn force_trust() -> Weight {
+		Weight::from_parts(21_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:1 w:3)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn force_trust_replacing_expired() -> Weight {
+		Weight::from_parts(72_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(21_u64))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:1 w:2)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:1 w:1)
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	fn force_untrust() -> Weight {
+		Weight::from_parts(53_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+	}
+	/// Storage: `TrustedAccounts::MigrationInProgress` (r:1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountListCount` (r:n+1 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountList` (r:2n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustingAccountIndex` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:n w:2n)
+	/// Storage: `TrustedAccounts::AccountTrustLevel` (r:0 w:n)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:0 w:n)
+	/// Storage: `Balances::Holds` (r:n w:n)
+	/// Storage: `System::Account` (r:n w:n)
+	/// Storage: `TrustedAccounts::AccountTrustDeposit` (r:n w:n)
+	fn purge_trustee(n: u32, ) -> Weight {
+		Weight::from_parts(11_000_000, 3593)
+			.saturating_add(Weight::from_parts(55_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((11_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((15_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:n w:0)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:n w:0)
+	fn trust_score(n: u32, ) -> Weight {
+		Weight::from_parts(2_000_000, 0)
+			.saturating_add(Weight::from_parts(7_000_000, 3593).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: `TrustedAccounts::AccountTrustedAccountIndex` (r:2n+1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustExpiry` (r:n+1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountListCount` (r:1 w:0)
+	/// Storage: `TrustedAccounts::AccountTrustedAccountList` (r:n w:0)
+	fn is_trusted_deep(n: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 3593)
+			.saturating_add(Weight::from_parts(14_000_000, 2500).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
+	}
+}